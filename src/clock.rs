@@ -0,0 +1,102 @@
+use std::time::{Duration, Instant};
+
+//  ===========
+//  FRAME CLOCK
+//  ===========
+
+/// The maximum number of simulation steps to catch up on in a single frame.
+/// Prevents the simulation from spiraling when frames take longer than the timestep.
+const MAX_STEPS_PER_FRAME: u32 = 5;
+
+/// A fixed-timestep frame clock.
+///
+/// The simulation advances in fixed steps of `1 / fps` seconds, independent of how long
+/// rendering takes or how often the main loop wakes up to handle input.
+pub struct FrameClock {
+    /// The duration of a single simulation step
+    timestep: Duration,
+    /// The time of the last tick
+    last_tick: Instant,
+    /// The time that has passed but hasn't been simulated yet
+    accumulator: Duration,
+
+    /// The number of frames rendered since `window_start`
+    frames: u32,
+    /// The start of the window over which the frame-rate is measured
+    window_start: Instant,
+    /// The measured frame-rate
+    measured_fps: f32,
+}
+
+impl FrameClock {
+    /// Constructs a new [FrameClock] ticking at the given frame-rate
+    pub fn new(fps: u16) -> Self {
+        let now = Instant::now();
+        Self {
+            timestep: Self::timestep_for(fps),
+            last_tick: now,
+            accumulator: Duration::ZERO,
+            frames: 0,
+            window_start: now,
+            measured_fps: 0.0,
+        }
+    }
+
+    /// Returns the duration of a single step at the given frame-rate
+    fn timestep_for(fps: u16) -> Duration {
+        Duration::from_secs_f64(1.0 / fps.max(1) as f64)
+    }
+
+    /// Changes the frame-rate the clock ticks at
+    pub fn set_fps(&mut self, fps: u16) {
+        self.timestep = Self::timestep_for(fps);
+    }
+
+    /// Returns the duration of a single simulation step in seconds
+    pub fn timestep(&self) -> f32 {
+        self.timestep.as_secs_f32()
+    }
+
+    /// Returns the time left until the next frame is due.
+    /// The time spent since the last tick (e.g. rendering) is accounted for.
+    pub fn time_until_next_frame(&self) -> Duration {
+        self.timestep
+            .saturating_sub(self.accumulator + self.last_tick.elapsed())
+    }
+
+    /// Advances the clock to the current time, and returns the number of simulation steps that are due
+    pub fn tick(&mut self) -> u32 {
+        let now = Instant::now();
+        self.accumulator += now - self.last_tick;
+        self.last_tick = now;
+
+        let mut steps = 0;
+        while self.accumulator >= self.timestep && steps < MAX_STEPS_PER_FRAME {
+            self.accumulator -= self.timestep;
+            steps += 1;
+        }
+
+        // Drop any time we couldn't catch up on, rather than trying to make up for it later
+        if steps == MAX_STEPS_PER_FRAME {
+            self.accumulator = Duration::ZERO;
+        }
+
+        steps
+    }
+
+    /// Records that a frame was rendered, updating the measured frame-rate once every second
+    pub fn frame_rendered(&mut self) {
+        self.frames += 1;
+        let elapsed = self.window_start.elapsed();
+        if elapsed >= Duration::from_secs(1) {
+            self.measured_fps = self.frames as f32 / elapsed.as_secs_f32();
+            self.frames = 0;
+            self.window_start = Instant::now();
+        }
+    }
+
+    /// Returns the measured frame-rate
+    pub fn measured_fps(&self) -> f32 {
+        self.measured_fps
+    }
+}
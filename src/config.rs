@@ -1,15 +1,47 @@
-use clap::Parser;
+use std::ffi::OsString;
+use std::path::PathBuf;
 
-use crate::helpers::{colors, direction};
-use crate::symbols;
+use clap::{parser::ValueSource, CommandFactory, Parser};
+
+use matrix_rain::helpers::{color_mode, colors, direction, distribution};
+use matrix_rain::{symbols, Options};
+
+use crate::themes;
 
 //  =============
 //  CONFIGURATION
 //  =============
 
 #[derive(Parser, Debug)]
-#[command(version, about)]
+#[command(version, about, args_override_self = true)]
 pub struct Config {
+    /// Path to the TOML configuration file
+    ///
+    /// Defaults to `$XDG_CONFIG_HOME/matrix-rain/config.toml` (or `~/.config/matrix-rain/config.toml`).
+    /// The keys in the file match the command-line options (e.g. `stream_color = "#00ff46"`).
+    /// Options passed on the command-line take precedence over the values in the file.
+    #[clap(long)]
+    pub config: Option<PathBuf>,
+
+    /// The named profile to use from the configuration file
+    ///
+    /// Profiles are defined as `[profile.<name>]` sections in the configuration file,
+    /// and their values take precedence over the top-level values.
+    #[clap(long)]
+    pub profile: Option<String>,
+
+    /// The built-in theme to use
+    ///
+    /// Themes set the --mode, --stream-color, --leading-entity-color, --stream-gradient and --background-color together.
+    /// Options given in the configuration file or on the command-line override those of the theme.
+    /// Use --list-themes to see the available themes.
+    #[clap(long, value_parser = themes::parse)]
+    pub theme: Option<&'static themes::Theme>,
+
+    /// Lists the built-in themes, with a preview of each
+    #[clap(long)]
+    pub list_themes: bool,
+
     /// The character symbol set to use
     ///
     /// Valid Options:
@@ -30,6 +62,9 @@ pub struct Config {
     ///
     /// The color gradually fades across the stream as given by the --stream-color-gradient-factor.
     ///
+    /// Colors can be given as "r,g,b", "#rrggbb", "#rgb", CSS functions like "rgb(0, 255, 70)",
+    /// "hsl(135, 100%, 50%)" and "hsv(135, 100%, 100%)", or CSS color names like "lime".
+    ///
     /// Defaults to the original matrix green color.
     #[clap(long, default_value = "0,255,70")]
     pub stream_color: colors::RGBColor,
@@ -40,22 +75,148 @@ pub struct Config {
     #[clap(long, default_value_t = 0.33)]
     pub stream_color_gradient_factor: f32,
 
+    /// The gradient the streams fade along, in place of the --stream-color and its gradient factor
+    ///
+    /// Given as a comma-separated list of color stops, each optionally followed by its position
+    /// between 0 and 1 (e.g. "#fff,#0f0@0.2,#003300" or "white,lime@20%,#003300").
+    /// Stops without a position are spaced evenly between their neighbours.
+    ///
+    /// Presets: "fire", "ice" and "sunset"
+    #[clap(long)]
+    pub stream_gradient: Option<colors::LinearGradient>,
+
+    /// The color space the stream gradient is interpolated in
+    ///
+    /// Valid Options:
+    /// - "srgb"       -> Interpolates the raw sRGB values
+    /// - "linear-rgb" -> Interpolates in linear light
+    /// - "hsl"        -> Interpolates the hue, saturation and lightness
+    /// - "oklab"      -> Interpolates in the perceptually uniform OKLab color space
+    #[clap(long, default_value = "srgb", verbatim_doc_comment)]
+    pub gradient_interpolation: colors::Interpolation,
+
     /// Color of the leading entity in a stream
     ///
     /// The leading entity in a stream can have a different color from the rest.
     #[clap(long, default_value = "200,255,200")]
     pub leading_entity_color: colors::RGBColor,
 
+    /// The color depth the colors are written to the terminal in
+    ///
+    /// Valid Options:
+    /// - "auto"      -> Detected from the COLORTERM and TERM environment variables
+    /// - "truecolor" -> 24-bit RGB colors
+    /// - "256"       -> The xterm 256 color palette
+    /// - "16"        -> The 16 basic ANSI colors
+    /// - "none"      -> No colors at all
+    ///
+    /// In "auto" mode, colors are disabled if the NO_COLOR environment variable is set.
+    #[clap(long, default_value = "auto", verbatim_doc_comment)]
+    pub color_mode: color_mode::ColorMode,
+
+    /// The background color behind the streams
+    ///
+    /// Fills every cell, including the empty ones. Defaults to the terminal's background.
+    #[clap(long)]
+    pub background_color: Option<colors::RGBColor>,
+
+    /// The gradient the background fades along from top to bottom, in place of the --background-color
+    ///
+    /// Given in the same format as the --stream-gradient (e.g. "#001a00,#000000").
+    #[clap(long)]
+    pub background_gradient: Option<colors::LinearGradient>,
+
+    /// Gives each stream a different hue, spreading the colors of the rainbow across the screen
+    ///
+    /// The hues are rotated from those of the --stream-color (or --stream-gradient) and --leading-entity-color.
+    #[clap(long, overrides_with = "no_rainbow")]
+    pub rainbow: bool,
+
+    /// Turns off --rainbow, e.g. when it's set in the configuration file
+    #[clap(long, overrides_with = "rainbow")]
+    pub no_rainbow: bool,
+
+    /// Rotates the hues of the streams over time, by this many degrees per second
+    ///
+    /// A value of 360 cycles through all the colors of the rainbow once a second.
+    #[clap(long, default_value_t = 0.0, value_name = "DEGREES_PER_SECOND")]
+    pub hue_cycle: f32,
+
     /// Leaves the trail intact
     ///
     /// As the streams pass-by, the leave behind a visible trail. Use this option if you want even more characters on screen.
-    #[clap(long)]
+    #[clap(long, overrides_with = "no_leave_trail")]
     pub leave_trail: bool,
 
+    /// Turns off --leave-trail, e.g. when it's set in the configuration file
+    #[clap(long, overrides_with = "leave_trail")]
+    pub no_leave_trail: bool,
+
     /// The Frame-Rate to run at. The screen will rerender this many times each second.
     #[clap(long, default_value_t = 60)]
     pub fps: u16,
 
+    /// Shows the measured frame-rate in the top-left corner
+    #[clap(long, overrides_with = "no_show_fps")]
+    pub show_fps: bool,
+
+    /// Turns off --show-fps, e.g. when it's set in the configuration file
+    #[clap(long, overrides_with = "show_fps")]
+    pub no_show_fps: bool,
+
+    /// Records the session to an asciicast v2 file (e.g. `session.cast`)
+    ///
+    /// The recording can be played back using `asciinema play session.cast`.
+    #[clap(long, value_name = "FILE")]
+    pub record: Option<PathBuf>,
+
+    /// Runs without a terminal, printing the frames to stdout instead
+    ///
+    /// Skips the alternate screen and raw mode. The number of frames is set by --frames,
+    /// and the size by --size. Frames are separated by an empty line.
+    #[clap(long, overrides_with = "no_headless")]
+    pub headless: bool,
+
+    /// Turns off --headless, e.g. when it's set in the configuration file
+    #[clap(long, overrides_with = "headless")]
+    pub no_headless: bool,
+
+    /// The format of the frames printed by --headless
+    #[clap(long, value_enum, default_value_t = HeadlessFormat::Ansi)]
+    pub headless_format: HeadlessFormat,
+
+    /// Only print the last frame in --headless mode
+    #[clap(long, overrides_with = "no_last_frame")]
+    pub last_frame: bool,
+
+    /// Turns off --last-frame, e.g. when it's set in the configuration file
+    #[clap(long, overrides_with = "last_frame")]
+    pub no_last_frame: bool,
+
+    /// Exports the animation as an animated GIF instead of running in the terminal
+    ///
+    /// Runs without a terminal, so it also works in CI. The number of frames is set by --frames,
+    /// and the size by --size.
+    #[clap(long, value_name = "FILE")]
+    pub export_gif: Option<PathBuf>,
+
+    /// Exports the animation as an animated SVG instead of running in the terminal
+    ///
+    /// The SVG is self-contained and animated using CSS, so it scales cleanly in documentation and web pages.
+    /// Keep the number of frames and the size modest, as every symbol of every frame is kept in the file.
+    #[clap(long, value_name = "FILE")]
+    pub export_svg: Option<PathBuf>,
+
+    /// The number of frames to export
+    #[clap(long, default_value_t = 300)]
+    pub frames: u32,
+
+    /// The size to export at, as COLUMNSxROWS (e.g. 80x24)
+    ///
+    /// Defaults to the size of the terminal, or 80x24 if there isn't one.
+    #[clap(long, value_name = "COLUMNSxROWS", value_parser = parse_size)]
+    pub size: Option<(u16, u16)>,
+
     /// Minimum number of entities per stream
     #[clap(long, default_value_t = 5)]
     pub stream_min_count: u16,
@@ -64,6 +225,48 @@ pub struct Config {
     #[clap(long, default_value_t = 25)]
     pub stream_max_count: u16,
 
+    /// Minimum speed of the streams, in cells per second
+    #[clap(long, default_value_t = 7.5)]
+    pub stream_min_speed: f32,
+
+    /// Maximum speed of the streams, in cells per second
+    #[clap(long, default_value_t = 60.0)]
+    pub stream_max_speed: f32,
+
+    /// How the speeds of the streams are distributed between the minimum and maximum speed
+    ///
+    /// Valid Options:
+    /// - "uniform" -> Every speed is equally likely
+    /// - "normal"  -> Most streams move at about the average speed
+    /// - "bimodal" -> The streams are split between slow and fast ones
+    #[clap(long, default_value = "uniform", verbatim_doc_comment)]
+    pub speed_distribution: distribution::Distribution,
+
+    /// The maximum distance, in cells, off-screen that the streams start at
+    ///
+    /// Staggers the first appearance of the streams. Zero starts them all at once.
+    #[clap(long, default_value_t = 50, value_name = "CELLS")]
+    pub spawn_window: u16,
+
+    /// The acceleration of the streams along their direction of motion, in cells per second squared
+    ///
+    /// Each stream is pulled by a random share of it, so the streams gradually pull apart.
+    /// Negative values slow the streams down until they turn back.
+    #[clap(long, default_value_t = 0.0, allow_hyphen_values = true)]
+    pub gravity: f32,
+
+    /// The maximum speed of the streams, in cells per second. Zero leaves their speed unlimited
+    #[clap(long, default_value_t = 0.0)]
+    pub terminal_velocity: f32,
+
+    /// The wind that blows the streams along, in cells per second (e.g. "-5,0" blows them to the left)
+    #[clap(long, default_value = "0,0", value_name = "X,Y", value_parser = parse_vector, allow_hyphen_values = true)]
+    pub wind: (f32, f32),
+
+    /// How much the strength of the wind varies in gusts, as a share of the wind (e.g. 0.5)
+    #[clap(long, default_value_t = 0.0)]
+    pub wind_gust: f32,
+
     /// The spacing between the streams.
     ///
     /// By default, a stream will be created every 2nd column.
@@ -78,6 +281,365 @@ pub struct Config {
     pub switch_interval: u16,
 
     /// The direction of motion for the stream particles
-    #[clap(long, default_value = "down")]
+    ///
+    /// Either one of the named directions ("down", "up", "left", "right", "diagonal-left", ...),
+    /// or any angle in degrees, where 0 is right, 90 is up, 180 is left and 270 is down.
+    /// Use "random" for each stream to flow in a direction of its own, or a weighted list of directions
+    /// (e.g. "down:0.8,up:0.2") for each stream to pick one of them every time it comes around.
+    #[clap(long, default_value = "down", allow_hyphen_values = true)]
     pub direction: direction::Direction,
+
+    /// The seed for the random number generator
+    ///
+    /// Runs with the same seed and terminal size produce identical output. Useful for reproducible demos and bug reports.
+    #[clap(long)]
+    pub seed: Option<u64>,
+}
+
+/// The format of the frames printed in headless mode
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum HeadlessFormat {
+    /// Plain text without any colors
+    Text,
+    /// Text with ANSI color codes
+    Ansi,
+}
+
+impl Config {
+    /// Loads the configuration from the configuration file and the command-line arguments.
+    /// The command-line arguments take precedence over the values in the configuration file,
+    /// which in turn take precedence over the values of the theme.
+    pub fn load() -> std::io::Result<Self> {
+        let args: Vec<OsString> = std::env::args_os().collect();
+
+        // Parse the command-line arguments first to determine the configuration file and profile
+        let config = Self::parse_from(&args);
+        let path = match &config.config {
+            Some(path) => path.clone(),
+            None => match default_path().filter(|path| path.exists()) {
+                Some(path) => path,
+                None if config.profile.is_some() => {
+                    return Err(std::io::Error::other(
+                        "No configuration file found to load the profile from",
+                    ))
+                }
+                None => return Self::parse_with_theme(args).validated(),
+            },
+        };
+
+        // Read the arguments from the configuration file
+        let contents = std::fs::read_to_string(&path)
+            .map_err(|e| std::io::Error::new(e.kind(), format!("{}: {e}", path.display())))?;
+        let file_args = file_args(&contents, config.profile.as_deref())
+            .map_err(|e| std::io::Error::other(format!("{}: {e}", path.display())))?;
+
+        // Pass the arguments from the file in before the command-line arguments, so that the latter override them
+        let args = args
+            .iter()
+            .take(1)
+            .cloned()
+            .chain(file_args.into_iter().map(OsString::from))
+            .chain(args.iter().skip(1).cloned())
+            .collect();
+        Self::parse_with_theme(args).validated()
+    }
+
+    /// Returns the configuration if it's valid
+    fn validated(self) -> std::io::Result<Self> {
+        self.validate().map_err(std::io::Error::other)?;
+        Ok(self)
+    }
+
+    /// Checks that the values of the options make sense together
+    pub fn validate(&self) -> Result<(), String> {
+        Options::from(self).validate().map_err(|e| e.to_string())
+    }
+
+    /// Parses the arguments, with the arguments of the selected theme (if any) passed in before them,
+    /// so that the options given explicitly override those of the theme
+    fn parse_with_theme(args: Vec<OsString>) -> Self {
+        let config = Self::parse_from(&args);
+        let Some(theme) = config.theme else {
+            return config;
+        };
+
+        // The gradient of the theme would hide a stream color given explicitly, so it's left out then
+        let matches = Self::command().get_matches_from(&args);
+        let given = |id| matches.value_source(id) == Some(ValueSource::CommandLine);
+        let gradient = !given("stream_color") && !given("stream_color_gradient_factor");
+
+        let args = args
+            .iter()
+            .take(1)
+            .cloned()
+            .chain(theme.args(gradient).into_iter().map(OsString::from))
+            .chain(args.iter().skip(1).cloned());
+        Self::parse_from(args)
+    }
+}
+
+/// Parses a size in the form of COLUMNSxROWS
+fn parse_size(s: &str) -> Result<(u16, u16), String> {
+    let invalid = || format!("Invalid size: {s} (expected COLUMNSxROWS, e.g. 80x24)");
+    let (columns, rows) = s.split_once(['x', 'X']).ok_or_else(invalid)?;
+    match (columns.trim().parse(), rows.trim().parse()) {
+        (Ok(columns), Ok(rows)) if columns > 0 && rows > 0 => Ok((columns, rows)),
+        _ => Err(invalid()),
+    }
+}
+
+/// Parses a vector in the form of X,Y
+fn parse_vector(s: &str) -> Result<(f32, f32), String> {
+    let invalid = || format!("Invalid vector: {s} (expected X,Y, e.g. -5,0)");
+    let (x, y) = s.split_once(',').ok_or_else(invalid)?;
+    match (x.trim().parse::<f32>(), y.trim().parse::<f32>()) {
+        (Ok(x), Ok(y)) if x.is_finite() && y.is_finite() => Ok((x, y)),
+        _ => Err(invalid()),
+    }
+}
+
+// -----------
+// CONFIG FILE
+// -----------
+
+/// Returns the default path to the configuration file
+fn default_path() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))
+        .map(|dir| dir.join("matrix-rain").join("config.toml"))
+}
+
+/// Converts the contents of a TOML configuration file into command-line arguments.
+/// The values of the selected profile take precedence over the top-level values.
+fn file_args(contents: &str, profile: Option<&str>) -> Result<Vec<String>, String> {
+    let mut table: toml::Table = contents.parse().map_err(|e| format!("{e}"))?;
+
+    // Separate the profiles from the top-level values
+    let profiles = match table.remove("profile") {
+        Some(toml::Value::Table(profiles)) => profiles,
+        Some(_) => return Err("`profile` must be a table of named profiles".into()),
+        None => toml::Table::new(),
+    };
+
+    // Merge the values of the profile over the top-level values
+    if let Some(name) = profile {
+        match profiles.get(name) {
+            Some(toml::Value::Table(values)) => table.extend(values.clone()),
+            Some(_) => return Err(format!("Profile `{name}` must be a table")),
+            None => return Err(format!("Unknown profile: {name}")),
+        }
+    }
+    table_args(&table)
+}
+
+/// Converts the key-value pairs of a TOML table into command-line arguments.
+/// Only the flags with a `--no-` counterpart can be turned off.
+fn table_args(table: &toml::Table) -> Result<Vec<String>, String> {
+    let command = Config::command();
+    let mut args = Vec::new();
+    for (key, value) in table {
+        let flag = format!("--{}", key.replace('_', "-"));
+        match value {
+            toml::Value::Boolean(true) => args.push(flag),
+            toml::Value::Boolean(false) => {
+                let negated = format!("no-{}", key.replace('_', "-"));
+                if !command
+                    .get_arguments()
+                    .any(|arg| arg.get_long() == Some(negated.as_str()))
+                {
+                    return Err(format!("`{key}` cannot be set to false in the config file"));
+                }
+                args.push(format!("--{negated}"))
+            }
+            toml::Value::String(s) => args.extend([flag, s.clone()]),
+            toml::Value::Integer(i) => args.extend([flag, i.to_string()]),
+            toml::Value::Float(f) => args.extend([flag, f.to_string()]),
+            _ => return Err(format!("Unsupported value for `{key}`: {value}")),
+        }
+    }
+    Ok(args)
+}
+
+impl From<&Config> for Options {
+    fn from(config: &Config) -> Self {
+        Self {
+            mode: config.mode.clone(),
+            stream_color: config.stream_color,
+            stream_color_gradient_factor: config.stream_color_gradient_factor,
+            stream_gradient: config.stream_gradient.clone(),
+            gradient_interpolation: config.gradient_interpolation,
+            leading_entity_color: config.leading_entity_color,
+            background_color: config.background_color,
+            background_gradient: config.background_gradient.clone(),
+            rainbow: config.rainbow,
+            hue_cycle: config.hue_cycle,
+            leave_trail: config.leave_trail,
+            fps: config.fps,
+            stream_min_count: config.stream_min_count,
+            stream_max_count: config.stream_max_count,
+            stream_min_speed: config.stream_min_speed,
+            stream_max_speed: config.stream_max_speed,
+            speed_distribution: config.speed_distribution,
+            spawn_window: config.spawn_window,
+            gravity: config.gravity,
+            terminal_velocity: config.terminal_velocity,
+            wind: config.wind,
+            wind_gust: config.wind_gust,
+            stream_spacing: config.stream_spacing,
+            switch_interval: config.switch_interval,
+            direction: config.direction.clone(),
+            seed: config.seed,
+        }
+    }
+}
+
+// -----
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENTS: &str = r##"
+        fps = 30
+        stream_color = "#00ff46"
+        leave_trail = false
+        rainbow = true
+
+        [profile.amber]
+        stream_color = "255,176,0"
+        leave_trail = true
+        stream_color_gradient_factor = 0.5
+
+        [profile.plain]
+        rainbow = false
+    "##;
+
+    /// Parses the arguments from the configuration file, followed by the command-line arguments
+    fn parse(profile: Option<&str>, args: &[&str]) -> Config {
+        let file_args = file_args(CONTENTS, profile).unwrap();
+        let args = std::iter::once("matrix-rain")
+            .chain(file_args.iter().map(String::as_str))
+            .chain(args.iter().copied());
+        Config::parse_from(args)
+    }
+
+    #[test]
+    fn should_convert_top_level_values_into_args() {
+        assert_eq!(
+            file_args(CONTENTS, None).unwrap(),
+            vec![
+                "--fps",
+                "30",
+                "--no-leave-trail",
+                "--rainbow",
+                "--stream-color",
+                "#00ff46"
+            ]
+        );
+    }
+
+    #[test]
+    fn should_merge_the_profile_values() {
+        assert_eq!(
+            file_args(CONTENTS, Some("amber")).unwrap(),
+            vec![
+                "--fps",
+                "30",
+                "--leave-trail",
+                "--rainbow",
+                "--stream-color",
+                "255,176,0",
+                "--stream-color-gradient-factor",
+                "0.5",
+            ]
+        );
+        assert!(file_args(CONTENTS, Some("unknown")).is_err());
+    }
+
+    #[test]
+    fn profiles_and_args_can_turn_off_flags() {
+        assert!(parse(None, &[]).rainbow);
+        assert!(!parse(Some("plain"), &[]).rainbow);
+        assert!(!parse(None, &["--no-rainbow"]).rainbow);
+        assert!(parse(Some("plain"), &["--rainbow"]).rainbow);
+        assert!(parse(Some("amber"), &[]).leave_trail);
+        assert!(!parse(Some("amber"), &["--no-leave-trail"]).leave_trail);
+
+        // Flags without a `--no-` counterpart can't be turned off in the config file
+        assert_eq!(
+            file_args("list_themes = false", None),
+            Err("`list_themes` cannot be set to false in the config file".into())
+        );
+    }
+
+    #[test]
+    fn should_parse_sizes() {
+        assert_eq!(parse_size("80x24"), Ok((80, 24)));
+        assert_eq!(parse_size("120X40"), Ok((120, 40)));
+        assert!(parse_size("80").is_err());
+        assert!(parse_size("0x24").is_err());
+    }
+
+    #[test]
+    fn should_parse_vectors() {
+        assert_eq!(parse_vector("-5,0"), Ok((-5.0, 0.0)));
+        assert_eq!(parse_vector("2.5, 1"), Ok((2.5, 1.0)));
+        assert!(parse_vector("5").is_err());
+        assert!(parse_vector("a,b").is_err());
+    }
+
+    #[test]
+    fn command_line_args_override_file_args() {
+        let config = Config::parse_from(["matrix-rain", "--fps", "30", "--fps", "120"]);
+        assert_eq!(config.fps, 120);
+    }
+
+    #[test]
+    fn args_override_the_theme() {
+        let args = ["matrix-rain", "--theme", "amber", "--stream-color", "red"];
+        let config = Config::parse_with_theme(args.into_iter().map(OsString::from).collect());
+        assert_eq!(config.stream_color, colors::RGBColor(255, 0, 0));
+        assert_eq!(config.leading_entity_color, "#ffe6a0".parse().unwrap());
+        assert!(matches!(config.mode, symbols::Symbols::ASCII));
+
+        // The stream color replaces the gradient of the theme
+        let red = colors::RGBColor(255, 0, 0);
+        let gradient = Options::from(&config).gradient();
+        assert_eq!(gradient.interpolate(0.0), red);
+        assert_eq!(gradient.interpolate(1.0), red * 0.33);
+
+        // Unless a gradient is given as well
+        let args = [&args[..], &["--stream-gradient", "#0f0,#00f"]].concat();
+        let config = Config::parse_with_theme(args.into_iter().map(OsString::from).collect());
+        let gradient = Options::from(&config).gradient();
+        assert_eq!(gradient.interpolate(1.0), colors::RGBColor(0, 0, 255));
+
+        // Otherwise, the stream fades along the gradient of the theme
+        let args = ["matrix-rain", "--theme", "amber"];
+        let config = Config::parse_with_theme(args.into_iter().map(OsString::from).collect());
+        let gradient = Options::from(&config).gradient();
+        assert_eq!(gradient.interpolate(1.0), "#663c00".parse().unwrap());
+    }
+
+    #[test]
+    fn should_validate_the_options() {
+        let validate = |args: &[&str]| {
+            let args = std::iter::once("matrix-rain").chain(args.iter().copied());
+            Config::parse_from(args).validate()
+        };
+        assert!(validate(&[]).is_ok());
+        assert!(validate(&["--stream-min-speed", "20", "--stream-max-speed", "20"]).is_ok());
+        assert!(validate(&["--stream-min-speed", "30", "--stream-max-speed", "20"]).is_err());
+        assert!(validate(&["--stream-min-speed", "0"]).is_err());
+        assert!(validate(&["--stream-min-count", "30"]).is_err());
+        assert!(validate(&["--stream-spacing", "0"]).is_err());
+        assert!(validate(&["--gravity", "-5", "--wind", "-5,0"]).is_ok());
+        assert!(validate(&["--terminal-velocity=-1"]).is_err());
+        assert!(validate(&["--wind-gust=-0.5"]).is_err());
+    }
 }
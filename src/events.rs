@@ -6,21 +6,44 @@ pub enum Action {
     None,
     /// Exit the loop
     Exit,
+    /// The terminal was resized to the given number of columns and rows
+    Resize(u16, u16),
+    /// Freeze the animation on the current frame
+    Pause,
+    /// Continue the animation
+    Resume,
+    /// Advance the paused animation by a single frame
+    Step,
+    /// Raise the frame-rate
+    IncreaseFps,
+    /// Lower the frame-rate
+    DecreaseFps,
+    /// Increase the spacing between the streams
+    IncreaseSpacing,
+    /// Decrease the spacing between the streams
+    DecreaseSpacing,
+    /// Switch to the next character symbol set
+    NextMode,
+    /// Switch to the next direction of motion
+    NextDirection,
+    /// Toggle whether the streams leave their trail intact
+    ToggleTrail,
 }
 
 /// Processes and handles [crossterm events](crossterm::event). Returns an [`Action`] as a response.
-pub fn handle_events() -> std::io::Result<Action> {
+pub fn handle_events(paused: bool) -> std::io::Result<Action> {
     match crossterm::event::read()? {
         crossterm::event::Event::Key(event) if event.kind == KeyEventKind::Press => {
-            return Ok(handle_key_event(event))
+            return Ok(handle_key_event(event, paused))
         }
+        crossterm::event::Event::Resize(columns, rows) => return Ok(Action::Resize(columns, rows)),
         _ => (),
     }
     Ok(Action::None)
 }
 
 /// Handles keyboard events and returns an [`Action`] based on the key pressed.
-fn handle_key_event(event: KeyEvent) -> Action {
+fn handle_key_event(event: KeyEvent, paused: bool) -> Action {
     match event {
         // Check if 'q', `Esc` or `Ctrl+C` has been pressed ...
         KeyEvent {
@@ -35,6 +58,59 @@ fn handle_key_event(event: KeyEvent) -> Action {
             modifiers: KeyModifiers::CONTROL,
             ..
         } => Action::Exit, // ... then respond with exit.
+
+        // The space bar toggles between pausing and resuming
+        KeyEvent {
+            code: KeyCode::Char(' '),
+            ..
+        } => {
+            if paused {
+                Action::Resume
+            } else {
+                Action::Pause
+            }
+        }
+
+        // While paused, '.' or the right arrow key steps a single frame
+        KeyEvent {
+            code: KeyCode::Char('.') | KeyCode::Right,
+            ..
+        } if paused => Action::Step,
+
+        // Adjust the frame-rate with '+' and '-'
+        KeyEvent {
+            code: KeyCode::Char('+' | '='),
+            ..
+        } => Action::IncreaseFps,
+        KeyEvent {
+            code: KeyCode::Char('-'),
+            ..
+        } => Action::DecreaseFps,
+
+        // Adjust the stream spacing with ']' and '['
+        KeyEvent {
+            code: KeyCode::Char(']'),
+            ..
+        } => Action::IncreaseSpacing,
+        KeyEvent {
+            code: KeyCode::Char('['),
+            ..
+        } => Action::DecreaseSpacing,
+
+        // Cycle the symbol set with 'm', the direction with 'd', and toggle the trail with 't'
+        KeyEvent {
+            code: KeyCode::Char('m'),
+            ..
+        } => Action::NextMode,
+        KeyEvent {
+            code: KeyCode::Char('d'),
+            ..
+        } => Action::NextDirection,
+        KeyEvent {
+            code: KeyCode::Char('t'),
+            ..
+        } => Action::ToggleTrail,
+
         _ => Action::None, // ... otherwise, respond with none.
     }
 }
@@ -0,0 +1,167 @@
+use std::io::Write;
+use std::time::Instant;
+
+//  ===========
+//  CAST WRITER
+//  ===========
+
+/// A [writer](Write) that passes everything through to an inner writer, while also recording
+/// it as an [asciicast v2](https://docs.asciinema.org/manual/asciicast/v2/) file.
+///
+/// Everything written between two flushes is recorded as a single timestamped output event.
+pub struct CastWriter<W: Write, C: Write> {
+    /// The writer everything is passed through to
+    inner: W,
+    /// The writer the asciicast is written to
+    cast: C,
+    /// The time the recording started
+    start: Instant,
+    /// The bytes written since the last flush
+    pending: Vec<u8>,
+}
+
+impl<W: Write, C: Write> CastWriter<W, C> {
+    /// Constructs a new [CastWriter] and writes the asciicast header for a terminal of the given size
+    pub fn new(inner: W, mut cast: C, columns: u16, rows: u16) -> std::io::Result<Self> {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        writeln!(
+            cast,
+            r#"{{"version": 2, "width": {columns}, "height": {rows}, "timestamp": {timestamp}}}"#
+        )?;
+        Ok(Self {
+            inner,
+            cast,
+            start: Instant::now(),
+            pending: Vec::new(),
+        })
+    }
+
+    /// Records a resize of the terminal to the given size, as a resize event.
+    /// The bytes written before the resize are recorded first, so that they play back at the old size.
+    pub fn resize(&mut self, columns: u16, rows: u16) -> std::io::Result<()> {
+        self.record()?;
+        let time = self.start.elapsed().as_secs_f64();
+        writeln!(self.cast, r#"[{time:.6}, "r", "{columns}x{rows}"]"#)
+    }
+
+    /// Records the pending bytes as an output event
+    fn record(&mut self) -> std::io::Result<()> {
+        // Hold back an incomplete UTF-8 sequence at the end until the rest of it comes in
+        let valid = match std::str::from_utf8(&self.pending) {
+            Ok(s) => s.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => self.pending.len(),
+        };
+        if valid == 0 {
+            return Ok(());
+        }
+
+        let data = String::from_utf8_lossy(&self.pending[..valid]).into_owned();
+        let time = self.start.elapsed().as_secs_f64();
+        writeln!(self.cast, r#"[{time:.6}, "o", "{}"]"#, escape_json(&data))?;
+        self.pending.drain(..valid);
+        Ok(())
+    }
+}
+
+impl<W: Write, C: Write> Write for CastWriter<W, C> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.pending.extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.record()?;
+        self.cast.flush()?;
+        self.inner.flush()
+    }
+}
+
+/// Escapes a string for use inside a JSON string literal
+fn escape_json(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// -----
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_escape_control_characters() {
+        assert_eq!(
+            escape_json("\x1b[1;1H\"ア\"\\"),
+            "\\u001b[1;1H\\\"ア\\\"\\\\"
+        );
+    }
+
+    #[test]
+    fn should_record_each_flush_as_an_output_event() {
+        let mut out = Vec::new();
+        let mut cast = Vec::new();
+        {
+            let mut writer = CastWriter::new(&mut out, &mut cast, 80, 24).unwrap();
+            writer.write_all(b"\x1b[1;1Ha").unwrap();
+            writer.flush().unwrap();
+            writer.flush().unwrap();
+        }
+        assert_eq!(out, b"\x1b[1;1Ha");
+
+        let cast = String::from_utf8(cast).unwrap();
+        let lines: Vec<&str> = cast.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with(r#"{"version": 2, "width": 80, "height": 24"#));
+        assert!(lines[1].starts_with('['));
+        assert!(lines[1].ends_with(r#", "o", "\u001b[1;1Ha"]"#));
+    }
+
+    #[test]
+    fn should_record_resizes_after_the_earlier_output() {
+        let mut cast = Vec::new();
+        {
+            let mut writer = CastWriter::new(std::io::sink(), &mut cast, 80, 24).unwrap();
+            writer.write_all(b"a").unwrap();
+            writer.resize(100, 30).unwrap();
+            writer.flush().unwrap();
+        }
+        let cast = String::from_utf8(cast).unwrap();
+        let lines: Vec<&str> = cast.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].ends_with(r#", "o", "a"]"#));
+        assert!(lines[2].ends_with(r#", "r", "100x30"]"#));
+    }
+
+    #[test]
+    fn should_hold_back_incomplete_utf8_sequences() {
+        let mut cast = Vec::new();
+        {
+            let symbol = "ア".as_bytes();
+            let mut writer = CastWriter::new(std::io::sink(), &mut cast, 80, 24).unwrap();
+            writer.write_all(&symbol[..1]).unwrap();
+            writer.flush().unwrap();
+            writer.write_all(&symbol[1..]).unwrap();
+            writer.flush().unwrap();
+        }
+        let cast = String::from_utf8(cast).unwrap();
+        assert_eq!(cast.lines().count(), 2);
+        assert!(cast.ends_with("\"ア\"]\n"));
+    }
+}
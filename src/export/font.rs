@@ -0,0 +1,342 @@
+//! A tiny built-in bitmap font, used to rasterize the cells of the [Matrix](crate::Matrix)
+//! when exporting to image formats.
+//!
+//! The printable ASCII glyphs are taken from the public domain X11 `6x10` misc-fixed font.
+//! The Katakana glyphs are drawn double-width, like they are in the terminal, and the Braille
+//! patterns are drawn from the dots their code points encode.
+//!
+//! Any other symbol (e.g. the mathematical symbols or emoji) is only approximated, by a made-up
+//! glyph derived from its code point, so that each symbol still has its own distinct shape.
+
+use unicode_width::UnicodeWidthChar;
+
+/// The width of a single cell of a glyph in pixels. Double-width glyphs are twice as wide
+pub const GLYPH_WIDTH: usize = 6;
+/// The height of a glyph in pixels
+pub const GLYPH_HEIGHT: usize = 10;
+
+/// A glyph bitmap, up to two cells wide. Each row holds the pixels of both cells, with the
+/// leftmost pixel in the 12th bit. Single-width glyphs only use the pixels of the left cell.
+pub type Glyph = [u16; GLYPH_HEIGHT];
+
+/// The bitmap of a single-width glyph. Each row is a byte, with the leftmost pixel in the 6th bit.
+type NarrowGlyph = [u8; GLYPH_HEIGHT];
+
+/// The glyphs for the printable ASCII characters from `' '` to `'~'`
+#[rustfmt::skip]
+const ASCII: [NarrowGlyph; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x00, 0x08, 0x08, 0x08, 0x08, 0x08, 0x00, 0x08, 0x00, 0x00], // '!'
+    [0x00, 0x14, 0x14, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // '"'
+    [0x00, 0x14, 0x14, 0x3e, 0x14, 0x3e, 0x14, 0x14, 0x00, 0x00], // '#'
+    [0x00, 0x08, 0x1c, 0x28, 0x1c, 0x0a, 0x1c, 0x08, 0x00, 0x00], // '$'
+    [0x00, 0x12, 0x2a, 0x14, 0x08, 0x14, 0x2a, 0x24, 0x00, 0x00], // '%'
+    [0x00, 0x10, 0x28, 0x28, 0x10, 0x2a, 0x24, 0x1a, 0x00, 0x00], // '&'
+    [0x00, 0x08, 0x08, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // '\''
+    [0x00, 0x04, 0x08, 0x10, 0x10, 0x10, 0x08, 0x04, 0x00, 0x00], // '('
+    [0x00, 0x10, 0x08, 0x04, 0x04, 0x04, 0x08, 0x10, 0x00, 0x00], // ')'
+    [0x00, 0x00, 0x22, 0x14, 0x3e, 0x14, 0x22, 0x00, 0x00, 0x00], // '*'
+    [0x00, 0x00, 0x08, 0x08, 0x3e, 0x08, 0x08, 0x00, 0x00, 0x00], // '+'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0c, 0x08, 0x10, 0x00], // ','
+    [0x00, 0x00, 0x00, 0x00, 0x3e, 0x00, 0x00, 0x00, 0x00, 0x00], // '-'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x08, 0x1c, 0x08, 0x00], // '.'
+    [0x00, 0x02, 0x02, 0x04, 0x08, 0x10, 0x20, 0x20, 0x00, 0x00], // '/'
+    [0x00, 0x08, 0x14, 0x22, 0x22, 0x22, 0x14, 0x08, 0x00, 0x00], // '0'
+    [0x00, 0x08, 0x18, 0x28, 0x08, 0x08, 0x08, 0x3e, 0x00, 0x00], // '1'
+    [0x00, 0x1c, 0x22, 0x02, 0x0c, 0x10, 0x20, 0x3e, 0x00, 0x00], // '2'
+    [0x00, 0x3e, 0x02, 0x04, 0x0c, 0x02, 0x22, 0x1c, 0x00, 0x00], // '3'
+    [0x00, 0x04, 0x0c, 0x14, 0x24, 0x3e, 0x04, 0x04, 0x00, 0x00], // '4'
+    [0x00, 0x3e, 0x20, 0x2c, 0x32, 0x02, 0x22, 0x1c, 0x00, 0x00], // '5'
+    [0x00, 0x0c, 0x10, 0x20, 0x2c, 0x32, 0x22, 0x1c, 0x00, 0x00], // '6'
+    [0x00, 0x3e, 0x02, 0x04, 0x04, 0x08, 0x10, 0x10, 0x00, 0x00], // '7'
+    [0x00, 0x1c, 0x22, 0x22, 0x1c, 0x22, 0x22, 0x1c, 0x00, 0x00], // '8'
+    [0x00, 0x1c, 0x22, 0x26, 0x1a, 0x02, 0x04, 0x18, 0x00, 0x00], // '9'
+    [0x00, 0x00, 0x08, 0x1c, 0x08, 0x00, 0x08, 0x1c, 0x08, 0x00], // ':'
+    [0x00, 0x00, 0x08, 0x1c, 0x08, 0x00, 0x0c, 0x08, 0x10, 0x00], // ';'
+    [0x00, 0x02, 0x04, 0x08, 0x10, 0x08, 0x04, 0x02, 0x00, 0x00], // '<'
+    [0x00, 0x00, 0x00, 0x3e, 0x00, 0x3e, 0x00, 0x00, 0x00, 0x00], // '='
+    [0x00, 0x10, 0x08, 0x04, 0x02, 0x04, 0x08, 0x10, 0x00, 0x00], // '>'
+    [0x00, 0x1c, 0x22, 0x04, 0x08, 0x08, 0x00, 0x08, 0x00, 0x00], // '?'
+    [0x00, 0x1c, 0x22, 0x26, 0x2a, 0x2c, 0x20, 0x1c, 0x00, 0x00], // '@'
+    [0x00, 0x08, 0x14, 0x22, 0x22, 0x3e, 0x22, 0x22, 0x00, 0x00], // 'A'
+    [0x00, 0x3c, 0x12, 0x12, 0x1c, 0x12, 0x12, 0x3c, 0x00, 0x00], // 'B'
+    [0x00, 0x1c, 0x22, 0x20, 0x20, 0x20, 0x22, 0x1c, 0x00, 0x00], // 'C'
+    [0x00, 0x3c, 0x12, 0x12, 0x12, 0x12, 0x12, 0x3c, 0x00, 0x00], // 'D'
+    [0x00, 0x3e, 0x20, 0x20, 0x3c, 0x20, 0x20, 0x3e, 0x00, 0x00], // 'E'
+    [0x00, 0x3e, 0x20, 0x20, 0x3c, 0x20, 0x20, 0x20, 0x00, 0x00], // 'F'
+    [0x00, 0x1c, 0x22, 0x20, 0x20, 0x26, 0x22, 0x1c, 0x00, 0x00], // 'G'
+    [0x00, 0x22, 0x22, 0x22, 0x3e, 0x22, 0x22, 0x22, 0x00, 0x00], // 'H'
+    [0x00, 0x1c, 0x08, 0x08, 0x08, 0x08, 0x08, 0x1c, 0x00, 0x00], // 'I'
+    [0x00, 0x0e, 0x04, 0x04, 0x04, 0x04, 0x24, 0x18, 0x00, 0x00], // 'J'
+    [0x00, 0x22, 0x24, 0x28, 0x30, 0x28, 0x24, 0x22, 0x00, 0x00], // 'K'
+    [0x00, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x3e, 0x00, 0x00], // 'L'
+    [0x00, 0x22, 0x22, 0x36, 0x2a, 0x22, 0x22, 0x22, 0x00, 0x00], // 'M'
+    [0x00, 0x22, 0x22, 0x32, 0x2a, 0x26, 0x22, 0x22, 0x00, 0x00], // 'N'
+    [0x00, 0x1c, 0x22, 0x22, 0x22, 0x22, 0x22, 0x1c, 0x00, 0x00], // 'O'
+    [0x00, 0x3c, 0x22, 0x22, 0x3c, 0x20, 0x20, 0x20, 0x00, 0x00], // 'P'
+    [0x00, 0x1c, 0x22, 0x22, 0x22, 0x22, 0x2a, 0x1c, 0x02, 0x00], // 'Q'
+    [0x00, 0x3c, 0x22, 0x22, 0x3c, 0x28, 0x24, 0x22, 0x00, 0x00], // 'R'
+    [0x00, 0x1c, 0x22, 0x20, 0x1c, 0x02, 0x22, 0x1c, 0x00, 0x00], // 'S'
+    [0x00, 0x3e, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x00, 0x00], // 'T'
+    [0x00, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x1c, 0x00, 0x00], // 'U'
+    [0x00, 0x22, 0x22, 0x22, 0x14, 0x14, 0x14, 0x08, 0x00, 0x00], // 'V'
+    [0x00, 0x22, 0x22, 0x22, 0x2a, 0x2a, 0x36, 0x22, 0x00, 0x00], // 'W'
+    [0x00, 0x22, 0x22, 0x14, 0x08, 0x14, 0x22, 0x22, 0x00, 0x00], // 'X'
+    [0x00, 0x22, 0x22, 0x14, 0x08, 0x08, 0x08, 0x08, 0x00, 0x00], // 'Y'
+    [0x00, 0x3e, 0x02, 0x04, 0x08, 0x10, 0x20, 0x3e, 0x00, 0x00], // 'Z'
+    [0x00, 0x1c, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1c, 0x00, 0x00], // '['
+    [0x00, 0x20, 0x20, 0x10, 0x08, 0x04, 0x02, 0x02, 0x00, 0x00], // '\\'
+    [0x00, 0x1c, 0x04, 0x04, 0x04, 0x04, 0x04, 0x1c, 0x00, 0x00], // ']'
+    [0x00, 0x08, 0x14, 0x22, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // '^'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x3e, 0x00], // '_'
+    [0x08, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // '`'
+    [0x00, 0x00, 0x00, 0x1c, 0x02, 0x1e, 0x22, 0x1e, 0x00, 0x00], // 'a'
+    [0x00, 0x20, 0x20, 0x2c, 0x32, 0x22, 0x32, 0x2c, 0x00, 0x00], // 'b'
+    [0x00, 0x00, 0x00, 0x1c, 0x22, 0x20, 0x22, 0x1c, 0x00, 0x00], // 'c'
+    [0x00, 0x02, 0x02, 0x1a, 0x26, 0x22, 0x26, 0x1a, 0x00, 0x00], // 'd'
+    [0x00, 0x00, 0x00, 0x1c, 0x22, 0x3e, 0x20, 0x1c, 0x00, 0x00], // 'e'
+    [0x00, 0x0c, 0x12, 0x10, 0x3c, 0x10, 0x10, 0x10, 0x00, 0x00], // 'f'
+    [0x00, 0x00, 0x00, 0x1e, 0x22, 0x22, 0x1e, 0x02, 0x22, 0x1c], // 'g'
+    [0x00, 0x20, 0x20, 0x2c, 0x32, 0x22, 0x22, 0x22, 0x00, 0x00], // 'h'
+    [0x00, 0x08, 0x00, 0x18, 0x08, 0x08, 0x08, 0x1c, 0x00, 0x00], // 'i'
+    [0x00, 0x02, 0x00, 0x06, 0x02, 0x02, 0x02, 0x12, 0x12, 0x0c], // 'j'
+    [0x00, 0x20, 0x20, 0x22, 0x24, 0x38, 0x24, 0x22, 0x00, 0x00], // 'k'
+    [0x00, 0x18, 0x08, 0x08, 0x08, 0x08, 0x08, 0x1c, 0x00, 0x00], // 'l'
+    [0x00, 0x00, 0x00, 0x34, 0x2a, 0x2a, 0x2a, 0x22, 0x00, 0x00], // 'm'
+    [0x00, 0x00, 0x00, 0x2c, 0x32, 0x22, 0x22, 0x22, 0x00, 0x00], // 'n'
+    [0x00, 0x00, 0x00, 0x1c, 0x22, 0x22, 0x22, 0x1c, 0x00, 0x00], // 'o'
+    [0x00, 0x00, 0x00, 0x2c, 0x32, 0x22, 0x32, 0x2c, 0x20, 0x20], // 'p'
+    [0x00, 0x00, 0x00, 0x1a, 0x26, 0x22, 0x26, 0x1a, 0x02, 0x02], // 'q'
+    [0x00, 0x00, 0x00, 0x2c, 0x32, 0x20, 0x20, 0x20, 0x00, 0x00], // 'r'
+    [0x00, 0x00, 0x00, 0x1c, 0x20, 0x1c, 0x02, 0x3c, 0x00, 0x00], // 's'
+    [0x00, 0x10, 0x10, 0x3c, 0x10, 0x10, 0x12, 0x0c, 0x00, 0x00], // 't'
+    [0x00, 0x00, 0x00, 0x22, 0x22, 0x22, 0x26, 0x1a, 0x00, 0x00], // 'u'
+    [0x00, 0x00, 0x00, 0x22, 0x22, 0x14, 0x14, 0x08, 0x00, 0x00], // 'v'
+    [0x00, 0x00, 0x00, 0x22, 0x22, 0x2a, 0x2a, 0x14, 0x00, 0x00], // 'w'
+    [0x00, 0x00, 0x00, 0x22, 0x14, 0x08, 0x14, 0x22, 0x00, 0x00], // 'x'
+    [0x00, 0x00, 0x00, 0x22, 0x22, 0x26, 0x1a, 0x02, 0x22, 0x1c], // 'y'
+    [0x00, 0x00, 0x00, 0x3e, 0x04, 0x08, 0x10, 0x3e, 0x00, 0x00], // 'z'
+    [0x00, 0x06, 0x08, 0x04, 0x18, 0x04, 0x08, 0x06, 0x00, 0x00], // '{'
+    [0x00, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x00, 0x00], // '|'
+    [0x00, 0x18, 0x04, 0x08, 0x06, 0x08, 0x04, 0x18, 0x00, 0x00], // '}'
+    [0x00, 0x12, 0x2a, 0x24, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // '~'
+];
+
+/// The double-width glyphs for the Katakana block, from `'゠'` (`0x30A0`) to `'ヿ'` (`0x30FF`)
+#[rustfmt::skip]
+const KATAKANA: [Glyph; 96] = [
+    [0x000, 0x000, 0x000, 0x1f0, 0x000, 0x1f0, 0x000, 0x000, 0x000, 0x000], // '゠'
+    [0x000, 0x000, 0x000, 0x3f8, 0x008, 0x050, 0x060, 0x040, 0x180, 0x000], // 'ァ'
+    [0x000, 0x7fc, 0x004, 0x058, 0x060, 0x040, 0x080, 0x100, 0x600, 0x000], // 'ア'
+    [0x000, 0x000, 0x000, 0x018, 0x060, 0x1a0, 0x020, 0x020, 0x020, 0x000], // 'ィ'
+    [0x000, 0x008, 0x010, 0x060, 0x1a0, 0x620, 0x020, 0x020, 0x020, 0x000], // 'イ'
+    [0x000, 0x000, 0x000, 0x040, 0x3f8, 0x208, 0x010, 0x020, 0x1c0, 0x000], // 'ゥ'
+    [0x000, 0x040, 0x7fc, 0x404, 0x404, 0x008, 0x010, 0x060, 0x380, 0x000], // 'ウ'
+    [0x000, 0x000, 0x000, 0x000, 0x1f0, 0x040, 0x040, 0x040, 0x3f8, 0x000], // 'ェ'
+    [0x000, 0x3f8, 0x040, 0x040, 0x040, 0x040, 0x040, 0x040, 0x7fc, 0x000], // 'エ'
+    [0x000, 0x000, 0x000, 0x020, 0x3f8, 0x060, 0x0a0, 0x120, 0x260, 0x000], // 'ォ'
+    [0x000, 0x010, 0x010, 0x7fc, 0x030, 0x050, 0x090, 0x310, 0x470, 0x000], // 'オ'
+    [0x000, 0x080, 0x080, 0x7fc, 0x084, 0x084, 0x104, 0x208, 0x430, 0x000], // 'カ'
+    [0x005, 0x085, 0x080, 0x7fc, 0x084, 0x084, 0x104, 0x208, 0x430, 0x000], // 'ガ'
+    [0x000, 0x040, 0x040, 0x3f8, 0x040, 0x7fc, 0x020, 0x020, 0x020, 0x000], // 'キ'
+    [0x005, 0x045, 0x040, 0x3f8, 0x040, 0x7fc, 0x020, 0x020, 0x020, 0x000], // 'ギ'
+    [0x000, 0x080, 0x1f8, 0x208, 0x408, 0x010, 0x020, 0x0c0, 0x300, 0x000], // 'ク'
+    [0x005, 0x085, 0x1f8, 0x208, 0x408, 0x010, 0x020, 0x0c0, 0x300, 0x000], // 'グ'
+    [0x000, 0x100, 0x100, 0x3f8, 0x420, 0x020, 0x020, 0x040, 0x180, 0x000], // 'ケ'
+    [0x005, 0x105, 0x100, 0x3f8, 0x420, 0x020, 0x020, 0x040, 0x180, 0x000], // 'ゲ'
+    [0x000, 0x000, 0x3f8, 0x008, 0x008, 0x008, 0x008, 0x3f8, 0x000, 0x000], // 'コ'
+    [0x005, 0x005, 0x3f8, 0x008, 0x008, 0x008, 0x008, 0x3f8, 0x000, 0x000], // 'ゴ'
+    [0x000, 0x110, 0x110, 0x7fc, 0x110, 0x110, 0x010, 0x020, 0x0c0, 0x000], // 'サ'
+    [0x005, 0x115, 0x110, 0x7fc, 0x110, 0x110, 0x010, 0x020, 0x0c0, 0x000], // 'ザ'
+    [0x000, 0x200, 0x104, 0x008, 0x410, 0x220, 0x040, 0x180, 0x600, 0x000], // 'シ'
+    [0x005, 0x205, 0x104, 0x008, 0x410, 0x220, 0x040, 0x180, 0x600, 0x000], // 'ジ'
+    [0x000, 0x000, 0x3f8, 0x008, 0x010, 0x020, 0x060, 0x190, 0x60c, 0x000], // 'ス'
+    [0x005, 0x005, 0x3f8, 0x008, 0x010, 0x020, 0x060, 0x190, 0x60c, 0x000], // 'ズ'
+    [0x000, 0x100, 0x100, 0x7fc, 0x108, 0x110, 0x100, 0x100, 0x0fc, 0x000], // 'セ'
+    [0x005, 0x105, 0x100, 0x7fc, 0x108, 0x110, 0x100, 0x100, 0x0fc, 0x000], // 'ゼ'
+    [0x000, 0x404, 0x204, 0x208, 0x010, 0x020, 0x040, 0x180, 0x600, 0x000], // 'ソ'
+    [0x005, 0x405, 0x204, 0x208, 0x010, 0x020, 0x040, 0x180, 0x600, 0x000], // 'ゾ'
+    [0x000, 0x080, 0x1f8, 0x208, 0x508, 0x0d0, 0x020, 0x0c0, 0x300, 0x000], // 'タ'
+    [0x005, 0x085, 0x1f8, 0x208, 0x508, 0x0d0, 0x020, 0x0c0, 0x300, 0x000], // 'ダ'
+    [0x000, 0x018, 0x3e0, 0x040, 0x7fc, 0x040, 0x040, 0x080, 0x300, 0x000], // 'チ'
+    [0x005, 0x01d, 0x3e0, 0x040, 0x7fc, 0x040, 0x040, 0x080, 0x300, 0x000], // 'ヂ'
+    [0x000, 0x000, 0x000, 0x248, 0x128, 0x010, 0x020, 0x040, 0x180, 0x000], // 'ッ'
+    [0x000, 0x484, 0x244, 0x248, 0x010, 0x020, 0x040, 0x180, 0x600, 0x000], // 'ツ'
+    [0x005, 0x485, 0x244, 0x248, 0x010, 0x020, 0x040, 0x180, 0x600, 0x000], // 'ヅ'
+    [0x000, 0x3f8, 0x000, 0x7fc, 0x040, 0x040, 0x040, 0x080, 0x300, 0x000], // 'テ'
+    [0x005, 0x3fd, 0x000, 0x7fc, 0x040, 0x040, 0x040, 0x080, 0x300, 0x000], // 'デ'
+    [0x000, 0x100, 0x100, 0x100, 0x1c0, 0x130, 0x100, 0x100, 0x100, 0x000], // 'ト'
+    [0x005, 0x105, 0x100, 0x100, 0x1c0, 0x130, 0x100, 0x100, 0x100, 0x000], // 'ド'
+    [0x000, 0x040, 0x040, 0x7fc, 0x040, 0x040, 0x040, 0x080, 0x300, 0x000], // 'ナ'
+    [0x000, 0x000, 0x3f8, 0x000, 0x000, 0x000, 0x000, 0x7fc, 0x000, 0x000], // 'ニ'
+    [0x000, 0x000, 0x7f8, 0x008, 0x110, 0x0a0, 0x040, 0x1b0, 0x608, 0x000], // 'ヌ'
+    [0x000, 0x040, 0x040, 0x7f0, 0x020, 0x060, 0x1a8, 0x624, 0x020, 0x000], // 'ネ'
+    [0x000, 0x004, 0x004, 0x008, 0x010, 0x020, 0x040, 0x180, 0x600, 0x000], // 'ノ'
+    [0x000, 0x000, 0x0a0, 0x090, 0x108, 0x108, 0x204, 0x204, 0x404, 0x000], // 'ハ'
+    [0x005, 0x005, 0x0a0, 0x090, 0x108, 0x108, 0x204, 0x204, 0x404, 0x000], // 'バ'
+    [0x002, 0x005, 0x0a2, 0x090, 0x108, 0x108, 0x204, 0x204, 0x404, 0x000], // 'パ'
+    [0x000, 0x200, 0x200, 0x218, 0x3e0, 0x200, 0x200, 0x200, 0x1f8, 0x000], // 'ヒ'
+    [0x005, 0x205, 0x200, 0x218, 0x3e0, 0x200, 0x200, 0x200, 0x1f8, 0x000], // 'ビ'
+    [0x002, 0x205, 0x202, 0x218, 0x3e0, 0x200, 0x200, 0x200, 0x1f8, 0x000], // 'ピ'
+    [0x000, 0x000, 0x7f8, 0x008, 0x008, 0x010, 0x020, 0x0c0, 0x300, 0x000], // 'フ'
+    [0x005, 0x005, 0x7f8, 0x008, 0x008, 0x010, 0x020, 0x0c0, 0x300, 0x000], // 'ブ'
+    [0x002, 0x005, 0x7fa, 0x008, 0x008, 0x010, 0x020, 0x0c0, 0x300, 0x000], // 'プ'
+    [0x000, 0x000, 0x000, 0x080, 0x140, 0x220, 0x410, 0x008, 0x004, 0x000], // 'ヘ'
+    [0x005, 0x005, 0x000, 0x080, 0x140, 0x220, 0x410, 0x008, 0x004, 0x000], // 'ベ'
+    [0x002, 0x005, 0x002, 0x080, 0x140, 0x220, 0x410, 0x008, 0x004, 0x000], // 'ペ'
+    [0x000, 0x040, 0x040, 0x7fc, 0x040, 0x150, 0x248, 0x444, 0x0c0, 0x000], // 'ホ'
+    [0x005, 0x045, 0x040, 0x7fc, 0x040, 0x150, 0x248, 0x444, 0x0c0, 0x000], // 'ボ'
+    [0x002, 0x045, 0x042, 0x7fc, 0x040, 0x150, 0x248, 0x444, 0x0c0, 0x000], // 'ポ'
+    [0x000, 0x000, 0x7fc, 0x008, 0x010, 0x160, 0x080, 0x040, 0x020, 0x000], // 'マ'
+    [0x000, 0x380, 0x060, 0x000, 0x380, 0x060, 0x000, 0x3c0, 0x038, 0x000], // 'ミ'
+    [0x000, 0x080, 0x080, 0x100, 0x110, 0x208, 0x204, 0x7fc, 0x000, 0x000], // 'ム'
+    [0x000, 0x008, 0x010, 0x220, 0x140, 0x080, 0x140, 0x220, 0x400, 0x000], // 'メ'
+    [0x000, 0x3f8, 0x080, 0x080, 0x7fc, 0x080, 0x080, 0x080, 0x07c, 0x000], // 'モ'
+    [0x000, 0x000, 0x000, 0x100, 0x3f8, 0x090, 0x0a0, 0x040, 0x040, 0x000], // 'ャ'
+    [0x000, 0x100, 0x100, 0x7fc, 0x088, 0x090, 0x040, 0x040, 0x020, 0x000], // 'ヤ'
+    [0x000, 0x000, 0x000, 0x000, 0x1e0, 0x020, 0x020, 0x020, 0x3f8, 0x000], // 'ュ'
+    [0x000, 0x000, 0x3f0, 0x010, 0x010, 0x010, 0x010, 0x7fc, 0x000, 0x000], // 'ユ'
+    [0x000, 0x000, 0x000, 0x3f0, 0x010, 0x1f0, 0x010, 0x010, 0x3f0, 0x000], // 'ョ'
+    [0x000, 0x000, 0x7f8, 0x008, 0x008, 0x3f8, 0x008, 0x008, 0x7f8, 0x000], // 'ヨ'
+    [0x000, 0x3f8, 0x000, 0x7fc, 0x004, 0x008, 0x010, 0x060, 0x380, 0x000], // 'ラ'
+    [0x000, 0x208, 0x208, 0x208, 0x208, 0x208, 0x010, 0x020, 0x0c0, 0x000], // 'リ'
+    [0x000, 0x120, 0x120, 0x120, 0x120, 0x120, 0x224, 0x228, 0x430, 0x000], // 'ル'
+    [0x000, 0x200, 0x200, 0x200, 0x200, 0x204, 0x208, 0x230, 0x3c0, 0x000], // 'レ'
+    [0x000, 0x000, 0x3f8, 0x208, 0x208, 0x208, 0x208, 0x3f8, 0x000, 0x000], // 'ロ'
+    [0x000, 0x000, 0x000, 0x3f8, 0x208, 0x208, 0x010, 0x020, 0x180, 0x000], // 'ヮ'
+    [0x000, 0x000, 0x7fc, 0x404, 0x404, 0x008, 0x010, 0x060, 0x180, 0x000], // 'ワ'
+    [0x000, 0x020, 0x7f8, 0x120, 0x120, 0x7fc, 0x020, 0x020, 0x020, 0x000], // 'ヰ'
+    [0x000, 0x3f8, 0x008, 0x050, 0x040, 0x040, 0x040, 0x040, 0x7fc, 0x000], // 'ヱ'
+    [0x000, 0x000, 0x7f8, 0x008, 0x7f0, 0x010, 0x020, 0x0c0, 0x300, 0x000], // 'ヲ'
+    [0x000, 0x400, 0x300, 0x004, 0x008, 0x010, 0x020, 0x0c0, 0x700, 0x000], // 'ン'
+    [0x005, 0x045, 0x7fc, 0x404, 0x404, 0x008, 0x010, 0x060, 0x380, 0x000], // 'ヴ'
+    [0x000, 0x000, 0x000, 0x080, 0x3f8, 0x088, 0x088, 0x110, 0x220, 0x000], // 'ヵ'
+    [0x000, 0x000, 0x000, 0x100, 0x3f8, 0x220, 0x020, 0x040, 0x180, 0x000], // 'ヶ'
+    [0x005, 0x005, 0x7fc, 0x404, 0x404, 0x008, 0x010, 0x060, 0x180, 0x000], // 'ヷ'
+    [0x005, 0x025, 0x7f8, 0x120, 0x120, 0x7fc, 0x020, 0x020, 0x020, 0x000], // 'ヸ'
+    [0x005, 0x3fd, 0x008, 0x050, 0x040, 0x040, 0x040, 0x040, 0x7fc, 0x000], // 'ヹ'
+    [0x005, 0x005, 0x7f8, 0x008, 0x7f0, 0x010, 0x020, 0x0c0, 0x300, 0x000], // 'ヺ'
+    [0x000, 0x000, 0x000, 0x000, 0x0e0, 0x0e0, 0x000, 0x000, 0x000, 0x000], // '・'
+    [0x000, 0x000, 0x000, 0x000, 0x000, 0x7fc, 0x000, 0x000, 0x000, 0x000], // 'ー'
+    [0x000, 0x000, 0x000, 0x100, 0x080, 0x040, 0x020, 0x000, 0x000, 0x000], // 'ヽ'
+    [0x005, 0x005, 0x000, 0x100, 0x080, 0x040, 0x020, 0x000, 0x000, 0x000], // 'ヾ'
+    [0x000, 0x000, 0x7f8, 0x008, 0x008, 0x008, 0x008, 0x008, 0x008, 0x000], // 'ヿ'
+];
+
+/// Returns the [Glyph] for the given symbol
+pub fn glyph(symbol: char) -> Glyph {
+    match symbol {
+        ' '..='~' => widen(ASCII[symbol as usize - ' ' as usize], 0),
+        '\u{30a0}'..='\u{30ff}' => KATAKANA[symbol as usize - 0x30a0],
+        '\u{2800}'..='\u{28ff}' => braille(symbol),
+        c if c.is_whitespace() || c.is_control() => [0; GLYPH_HEIGHT],
+        // Made-up glyphs are centered across both cells of double-width symbols
+        c if c.width() == Some(2) => widen(made_up_glyph(c), GLYPH_WIDTH / 2),
+        c => widen(made_up_glyph(c), 0),
+    }
+}
+
+/// Converts a single-width glyph into a [Glyph], moved to the right by the given number of pixels
+fn widen(glyph: NarrowGlyph, offset: usize) -> Glyph {
+    glyph.map(|row| (row as u16) << (GLYPH_WIDTH - offset))
+}
+
+/// Draws the dots of a Braille pattern. The bits of the code point select the dots,
+/// from the top of the left column down, then the right column, and the bottom two last.
+fn braille(symbol: char) -> Glyph {
+    let bits = symbol as u32 - 0x2800;
+    // The (row, column) of each dot, in the order of the bits
+    const DOTS: [(usize, usize); 8] = [
+        (1, 1),
+        (3, 1),
+        (5, 1),
+        (1, 4),
+        (3, 4),
+        (5, 4),
+        (7, 1),
+        (7, 4),
+    ];
+
+    let mut glyph = [0; GLYPH_HEIGHT];
+    for (bit, (row, column)) in DOTS.iter().enumerate() {
+        if bits & (1 << bit) != 0 {
+            glyph[*row] |= 1 << (2 * GLYPH_WIDTH - 1 - column);
+        }
+    }
+    glyph
+}
+
+/// Derives a symmetric 5x7 glyph from the code point of the symbol.
+/// The same symbol always produces the same glyph.
+fn made_up_glyph(symbol: char) -> NarrowGlyph {
+    // A simple xorshift, seeded with the code point, to pick the pixels
+    let mut state = (symbol as u32).wrapping_mul(0x9e37_79b9) | 1;
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state
+    };
+
+    let mut glyph = [0; GLYPH_HEIGHT];
+    for row in glyph.iter_mut().skip(1).take(7) {
+        // Pick the left three columns and mirror them onto the right two
+        let left = (next() >> 7) as u8 & 0b111;
+        let mirrored = ((left & 0b010) << 1) | ((left & 0b100) >> 1);
+        *row = ((left << 2) | (mirrored >> 1)) << 1;
+    }
+    glyph
+}
+
+// -----
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_map_ascii_characters_to_their_glyphs() {
+        assert_eq!(glyph(' '), [0; GLYPH_HEIGHT]);
+        assert_eq!(
+            glyph('A').map(|row| row >> GLYPH_WIDTH),
+            [0x00, 0x08, 0x14, 0x22, 0x22, 0x3e, 0x22, 0x22, 0x00, 0x00]
+        );
+        assert!(glyph('A').iter().all(|row| row & 0x3f == 0));
+    }
+
+    #[test]
+    fn should_draw_katakana_across_both_cells() {
+        // The top bar of 'ア' spans both cells
+        assert_eq!(glyph('ア')[1], 0x7fc);
+        assert!(KATAKANA
+            .iter()
+            .all(|glyph| glyph.iter().any(|row| *row != 0)));
+
+        // The voiced Katakana carry the dakuten on top of their unvoiced glyph
+        let dakuten = [0x005, 0x005, 0, 0, 0, 0, 0, 0, 0, 0];
+        let voiced: Vec<u16> = (0..GLYPH_HEIGHT)
+            .map(|i| glyph('カ')[i] | dakuten[i])
+            .collect();
+        assert_eq!(glyph('ガ').to_vec(), voiced);
+    }
+
+    #[test]
+    fn should_draw_the_dots_of_braille_patterns() {
+        assert_eq!(glyph('⠁')[1], 1 << 10);
+        assert_eq!(
+            glyph('⣿').iter().map(|row| row.count_ones()).sum::<u32>(),
+            8
+        );
+    }
+
+    #[test]
+    fn should_make_up_stable_glyphs_for_other_symbols() {
+        assert_eq!(glyph('∑'), glyph('∑'));
+        assert_ne!(glyph('∑'), glyph('≠'));
+        assert!(glyph('∑')
+            .iter()
+            .all(|row| row & !(0x3e << GLYPH_WIDTH) == 0));
+    }
+}
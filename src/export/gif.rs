@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+use std::io::Write;
+
+use super::font;
+use crate::helpers::colors::RGBColor;
+use crate::renderer::Buffer;
+
+//  ============
+//  GIF EXPORTER
+//  ============
+
+/// The color of the background where the [cells](crate::Cell) don't specify one
+const DEFAULT_BACKGROUND: RGBColor = RGBColor(0, 0, 0);
+/// The color of the symbols where the [cells](crate::Cell) don't specify one
+const DEFAULT_FOREGROUND: RGBColor = RGBColor(204, 204, 204);
+
+/// Exports frames of the [Matrix](crate::Matrix) as an endlessly looping animated GIF.
+/// Each cell is rasterized using the built-in [bitmap font](font).
+pub struct GifExporter<W: Write> {
+    /// The GIF encoder that the frames are written to
+    encoder: gif::Encoder<W>,
+    /// The number of rows of cells in a frame
+    rows: u16,
+    /// The number of columns of cells in a frame
+    columns: u16,
+    /// The delay between frames, in hundredths of a second
+    delay: u16,
+}
+
+impl<W: Write> GifExporter<W> {
+    /// Constructs a new [GifExporter] for frames of the given size, and writes the GIF header.
+    ///
+    /// GIFs measure the frame delay in hundredths of a second, and most viewers don't
+    /// play frames faster than 50 per second. So the frame-rate is capped accordingly, and
+    /// [`frame_duration`](Self::frame_duration) returns the actual time each frame is shown for.
+    pub fn new(out: W, rows: u16, columns: u16, fps: u16) -> std::io::Result<Self> {
+        let (width, height) = Self::dimensions(rows, columns)?;
+        let mut encoder = gif::Encoder::new(out, width, height, &[]).map_err(to_io_error)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(to_io_error)?;
+        Ok(Self {
+            encoder,
+            rows,
+            columns,
+            delay: (100.0 / fps.max(1) as f32).round().max(2.0) as u16,
+        })
+    }
+
+    /// Returns the dimensions of the image in pixels for the given number of rows and columns
+    fn dimensions(rows: u16, columns: u16) -> std::io::Result<(u16, u16)> {
+        let width = u16::try_from(columns as usize * font::GLYPH_WIDTH);
+        let height = u16::try_from(rows as usize * font::GLYPH_HEIGHT);
+        match (width, height) {
+            (Ok(width), Ok(height)) => Ok((width, height)),
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("{columns}x{rows} is too large for a GIF"),
+            )),
+        }
+    }
+
+    /// Returns the number of seconds each frame is shown for
+    pub fn frame_duration(&self) -> f32 {
+        self.delay as f32 / 100.0
+    }
+
+    /// Rasterizes the [Buffer] and adds it as the next frame of the GIF
+    pub fn add_frame(&mut self, buffer: &Buffer) -> std::io::Result<()> {
+        let (width, height) = Self::dimensions(self.rows, self.columns)?;
+        let pixels = rasterize(buffer, self.rows, self.columns);
+
+        // Use the exact colors if they fit in a palette, otherwise let the encoder quantize them
+        let mut frame = indexed_frame(width, height, &pixels)
+            .unwrap_or_else(|| gif::Frame::from_rgb_speed(width, height, &pixels, 10));
+        frame.delay = self.delay;
+
+        self.encoder.write_frame(&frame).map_err(to_io_error)
+    }
+}
+
+/// Rasterizes the cells of the [Buffer] into RGB pixels
+fn rasterize(buffer: &Buffer, rows: u16, columns: u16) -> Vec<u8> {
+    let width = columns as usize * font::GLYPH_WIDTH;
+    let mut pixels = vec![0; width * rows as usize * font::GLYPH_HEIGHT * 3];
+
+    for y in 0..rows {
+        for x in 0..columns {
+            let cell = buffer.get(x, y).copied().unwrap_or_default();
+            // The continuation cells are drawn along with their double-width symbol
+            if cell.is_continuation() {
+                continue;
+            }
+            let fg = cell.fg.unwrap_or(DEFAULT_FOREGROUND);
+            let bg = cell.bg.unwrap_or(DEFAULT_BACKGROUND);
+            let glyph = font::glyph(cell.symbol);
+            let cells = (cell.width() as usize).min((columns - x) as usize);
+
+            for (row, bits) in glyph.iter().enumerate() {
+                let py = y as usize * font::GLYPH_HEIGHT + row;
+                for col in 0..cells * font::GLYPH_WIDTH {
+                    let px = x as usize * font::GLYPH_WIDTH + col;
+                    let lit = bits & (1 << (2 * font::GLYPH_WIDTH - 1 - col)) != 0;
+                    let color = if lit { fg } else { bg };
+                    let i = (py * width + px) * 3;
+                    pixels[i..i + 3].copy_from_slice(&[color.r(), color.g(), color.b()]);
+                }
+            }
+        }
+    }
+
+    pixels
+}
+
+/// Builds a frame with an exact palette, if the pixels use no more than 256 colors
+fn indexed_frame(width: u16, height: u16, pixels: &[u8]) -> Option<gif::Frame<'static>> {
+    let mut palette = Vec::new();
+    let mut indices: HashMap<[u8; 3], u8> = HashMap::new();
+    let mut indexed = Vec::with_capacity(pixels.len() / 3);
+
+    for rgb in pixels.chunks_exact(3) {
+        let rgb = [rgb[0], rgb[1], rgb[2]];
+        let index = match indices.get(&rgb) {
+            Some(index) => *index,
+            None => {
+                let index = u8::try_from(indices.len()).ok()?;
+                indices.insert(rgb, index);
+                palette.extend_from_slice(&rgb);
+                index
+            }
+        };
+        indexed.push(index);
+    }
+
+    Some(gif::Frame::from_palette_pixels(
+        width, height, indexed, palette, None,
+    ))
+}
+
+/// Converts a GIF encoding error into an IO error
+fn to_io_error(error: gif::EncodingError) -> std::io::Error {
+    match error {
+        gif::EncodingError::Io(e) => e,
+        e => std::io::Error::other(e),
+    }
+}
+
+// -----
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::Cell;
+
+    #[test]
+    fn should_rasterize_cells_with_their_colors() {
+        let mut buffer = Buffer::new(1, 2);
+        buffer.set(1, 0, Cell::new('A', RGBColor(0, 255, 0)));
+        let pixels = rasterize(&buffer, 1, 2);
+        assert_eq!(pixels.len(), 12 * 10 * 3);
+
+        // The top of the 'A' sits in the third column of the second row of the glyph
+        let pixel = |x: usize, y: usize| &pixels[(y * 12 + x) * 3..(y * 12 + x) * 3 + 3];
+        assert_eq!(pixel(6 + 2, 1), &[0, 255, 0]);
+        assert_eq!(pixel(6 + 2, 0), &[0, 0, 0]);
+        assert_eq!(pixel(2, 1), &[0, 0, 0]);
+
+        // Double-width symbols are drawn across both of their cells
+        buffer.set(0, 0, Cell::new('ア', RGBColor(0, 255, 0)));
+        let pixels = rasterize(&buffer, 1, 2);
+        let pixel = |x: usize, y: usize| &pixels[(y * 12 + x) * 3..(y * 12 + x) * 3 + 3];
+        assert_eq!(pixel(1, 1), &[0, 255, 0]);
+        assert_eq!(pixel(9, 1), &[0, 255, 0]);
+        assert_eq!(pixel(11, 1), &[0, 0, 0]);
+    }
+
+    #[test]
+    fn should_write_a_gif() {
+        let mut buffer = Buffer::new(2, 3);
+        buffer.set(0, 0, Cell::new('ア', RGBColor(0, 255, 70)));
+        let mut out = Vec::new();
+        {
+            let mut exporter = GifExporter::new(&mut out, 2, 3, 60).unwrap();
+            assert_eq!(exporter.frame_duration(), 0.02);
+            exporter.add_frame(&buffer).unwrap();
+            exporter.add_frame(&Buffer::new(2, 3)).unwrap();
+        }
+        assert!(out.starts_with(b"GIF89a"));
+        assert_eq!(out.last(), Some(&0x3b)); // The GIF trailer
+    }
+}
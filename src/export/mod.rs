@@ -0,0 +1,13 @@
+//! Exporters that capture the [Matrix](crate::Matrix) in other formats
+
+pub mod font;
+
+mod cast;
+mod gif;
+mod svg;
+mod text;
+
+pub use self::gif::GifExporter;
+pub use cast::CastWriter;
+pub use svg::SvgExporter;
+pub use text::{to_ansi, to_text};
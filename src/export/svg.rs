@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::io::Write;
+
+use crate::helpers::colors::RGBColor;
+use crate::renderer::Buffer;
+
+//  ============
+//  SVG EXPORTER
+//  ============
+
+/// The width of a cell in pixels
+const CELL_WIDTH: usize = 10;
+/// The height of a cell in pixels
+const CELL_HEIGHT: usize = 18;
+/// The font size of the symbols in pixels
+const FONT_SIZE: usize = 15;
+
+/// The color of the background where the [cells](crate::Cell) don't specify one
+const DEFAULT_BACKGROUND: RGBColor = RGBColor(0, 0, 0);
+/// The color of the symbols where the [cells](crate::Cell) don't specify one
+const DEFAULT_FOREGROUND: RGBColor = RGBColor(204, 204, 204);
+
+/// Exports frames of the [Matrix](crate::Matrix) as a self-contained, endlessly looping animated SVG.
+///
+/// Each frame is a group of `<text>` elements, one for every symbol, that a CSS animation
+/// shows in turn. The colors are kept exactly as they are in the [cells](crate::Cell).
+pub struct SvgExporter {
+    /// The number of rows of cells in a frame
+    rows: u16,
+    /// The number of columns of cells in a frame
+    columns: u16,
+    /// The number of seconds each frame is shown for
+    frame_duration: f32,
+    /// The markup of the frames added so far
+    frames: Vec<String>,
+    /// The CSS class index of every color used so far
+    colors: HashMap<RGBColor, usize>,
+}
+
+impl SvgExporter {
+    /// Constructs a new [SvgExporter] for frames of the given size, played at the given frame-rate
+    pub fn new(rows: u16, columns: u16, fps: u16) -> Self {
+        Self {
+            rows,
+            columns,
+            frame_duration: 1.0 / fps.max(1) as f32,
+            frames: Vec::new(),
+            colors: HashMap::new(),
+        }
+    }
+
+    /// Returns the number of seconds each frame is shown for
+    pub fn frame_duration(&self) -> f32 {
+        self.frame_duration
+    }
+
+    /// Returns the CSS class of the given color, registering it if it's new
+    fn class(&mut self, color: RGBColor) -> String {
+        let next = self.colors.len();
+        format!("c{}", self.colors.entry(color).or_insert(next))
+    }
+
+    /// Adds the [Buffer] as the next frame of the SVG
+    pub fn add_frame(&mut self, buffer: &Buffer) {
+        let mut markup = String::new();
+        for y in 0..self.rows {
+            for x in 0..self.columns {
+                let Some(cell) = buffer.get(x, y) else {
+                    continue;
+                };
+                let (px, py) = (x as usize * CELL_WIDTH, y as usize * CELL_HEIGHT);
+
+                if let Some(bg) = cell.bg {
+                    let class = self.class(bg);
+                    let _ = write!(
+                        markup,
+                        r#"<rect class="{class}" x="{px}" y="{py}" width="{CELL_WIDTH}" height="{CELL_HEIGHT}"/>"#
+                    );
+                }
+
+                if !cell.symbol.is_whitespace() && !cell.symbol.is_control() {
+                    let class = self.class(cell.fg.unwrap_or(DEFAULT_FOREGROUND));
+                    let _ = write!(
+                        markup,
+                        r#"<text class="{class}" x="{}" y="{}">{}</text>"#,
+                        px + CELL_WIDTH / 2,
+                        py + CELL_HEIGHT * 3 / 4,
+                        escape_xml(cell.symbol)
+                    );
+                }
+            }
+        }
+        self.frames.push(markup);
+    }
+
+    /// Writes the SVG with all the frames added so far
+    pub fn finish<W: Write>(&self, mut out: W) -> std::io::Result<()> {
+        let width = self.columns as usize * CELL_WIDTH;
+        let height = self.rows as usize * CELL_HEIGHT;
+        let count = self.frames.len().max(1);
+        let total = self.frame_duration * count as f32;
+
+        writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+        )?;
+
+        // Every frame is hidden, except for its own slice of the animation
+        writeln!(out, "<style>")?;
+        writeln!(
+            out,
+            "text {{ font-family: monospace; font-size: {FONT_SIZE}px; text-anchor: middle; }}"
+        )?;
+        writeln!(
+            out,
+            "g {{ visibility: hidden; animation: frame {total:.3}s step-end infinite; }}"
+        )?;
+        writeln!(
+            out,
+            "@keyframes frame {{ 0% {{ visibility: visible; }} {:.4}% {{ visibility: hidden; }} }}",
+            100.0 / count as f32
+        )?;
+        let mut colors: Vec<_> = self.colors.iter().collect();
+        colors.sort_by_key(|(_, index)| **index);
+        for (color, index) in colors {
+            writeln!(out, ".c{index} {{ fill: {}; }}", hex(*color))?;
+        }
+        writeln!(out, "</style>")?;
+
+        writeln!(
+            out,
+            r#"<rect width="100%" height="100%" fill="{}"/>"#,
+            hex(DEFAULT_BACKGROUND)
+        )?;
+        for (i, frame) in self.frames.iter().enumerate() {
+            writeln!(
+                out,
+                r#"<g style="animation-delay: {:.3}s">{frame}</g>"#,
+                i as f32 * self.frame_duration
+            )?;
+        }
+        writeln!(out, "</svg>")
+    }
+}
+
+/// Formats the color as a hex color code
+fn hex(color: RGBColor) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b())
+}
+
+/// Escapes a symbol for use in XML text
+fn escape_xml(symbol: char) -> String {
+    match symbol {
+        '&' => "&amp;".into(),
+        '<' => "&lt;".into(),
+        '>' => "&gt;".into(),
+        '"' => "&quot;".into(),
+        '\'' => "&apos;".into(),
+        c => c.to_string(),
+    }
+}
+
+// -----
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::Cell;
+
+    #[test]
+    fn should_write_each_frame_as_an_animated_group() {
+        let green = RGBColor(0, 255, 70);
+        let mut exporter = SvgExporter::new(2, 3, 50);
+
+        let mut buffer = Buffer::new(2, 3);
+        buffer.set(1, 0, Cell::new('<', green));
+        exporter.add_frame(&buffer);
+        buffer.set(1, 1, Cell::new('ア', RGBColor(0, 84, 23)));
+        exporter.add_frame(&buffer);
+
+        let mut out = Vec::new();
+        exporter.finish(&mut out).unwrap();
+        let svg = String::from_utf8(out).unwrap();
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert!(svg.contains("animation: frame 0.040s step-end infinite"));
+        assert!(svg.contains(".c0 { fill: #00ff46; }"));
+        assert!(svg.contains(".c1 { fill: #005417; }"));
+        assert!(svg.contains(r#"<text class="c0" x="15" y="13">&lt;</text>"#));
+        assert!(svg.contains(r#"<g style="animation-delay: 0.020s">"#));
+        assert_eq!(svg.matches("<text").count(), 3);
+    }
+}
@@ -0,0 +1,94 @@
+use std::fmt::Write as _;
+
+use crossterm::style::{Color, ResetColor};
+use crossterm::Command;
+
+use crate::helpers::color_mode::ColorMode;
+use crate::renderer::{to_color, Buffer, SetBackground, SetForeground};
+
+//  ====
+//  TEXT
+//  ====
+
+/// Formats the [Buffer] as plain text, one line per row, with trailing whitespace removed
+pub fn to_text(buffer: &Buffer) -> String {
+    let mut text = String::new();
+    for y in 0..buffer.rows() {
+        let line: String = (0..buffer.columns())
+            .filter_map(|x| buffer.get(x, y))
+            .filter(|cell| !cell.is_continuation())
+            .map(|cell| cell.symbol)
+            .collect();
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+/// Formats the [Buffer] as text with ANSI color codes, one line per row.
+/// The colors are written in the given [ColorMode], and reset at the end of every line,
+/// so each line stands on its own.
+pub fn to_ansi(buffer: &Buffer, color_mode: ColorMode) -> String {
+    let color_mode = color_mode.resolve();
+    let mut text = String::new();
+    for y in 0..buffer.rows() {
+        // The colors currently set
+        let mut fg = Color::Reset;
+        let mut bg = Color::Reset;
+
+        let cells = (0..buffer.columns()).filter_map(|x| buffer.get(x, y));
+        for cell in cells.filter(|cell| !cell.is_continuation()) {
+            // Writing into a String can't fail
+            let cell_fg = to_color(cell.fg, color_mode);
+            if cell_fg != fg {
+                let _ = SetForeground(cell_fg).write_ansi(&mut text);
+                fg = cell_fg;
+            }
+            let cell_bg = to_color(cell.bg, color_mode);
+            if cell_bg != bg {
+                let _ = SetBackground(cell_bg).write_ansi(&mut text);
+                bg = cell_bg;
+            }
+            text.push(cell.symbol);
+        }
+
+        if fg != Color::Reset || bg != Color::Reset {
+            let _ = ResetColor.write_ansi(&mut text);
+        }
+        let _ = writeln!(text);
+    }
+    text
+}
+
+// -----
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::colors::RGBColor;
+    use crate::renderer::Cell;
+
+    fn frame() -> Buffer {
+        let green = RGBColor(0, 255, 0);
+        let mut buffer = Buffer::new(2, 4);
+        buffer.set(0, 0, Cell::new('a', green));
+        buffer.set(1, 0, Cell::new('b', green));
+        buffer.set(2, 1, Cell::new('c', green));
+        buffer
+    }
+
+    #[test]
+    fn should_format_as_plain_text() {
+        assert_eq!(to_text(&frame()), "ab\n  c\n");
+    }
+
+    #[test]
+    fn should_format_as_ansi_text() {
+        assert_eq!(
+            to_ansi(&frame(), ColorMode::TrueColor),
+            "\x1b[38;2;0;255;0mab\x1b[39m  \n  \x1b[38;2;0;255;0mc\x1b[39m \n"
+        );
+    }
+}
@@ -0,0 +1,90 @@
+use std::io::Write;
+
+use matrix_rain::{
+    export::{self, GifExporter, SvgExporter},
+    Buffer, Matrix,
+};
+
+use crate::config;
+
+//  ========
+//  HEADLESS
+//  ========
+
+/// Determine the number of rows and columns to run at without a terminal.
+/// Uses the `--size` option, falling back to the terminal size and then 80x24.
+fn size(config: &config::Config) -> (u16, u16) {
+    config
+        .size
+        .or_else(|| crossterm::terminal::size().ok())
+        .unwrap_or((80, 24))
+}
+
+/// Runs the Matrix simulation without a terminal, and prints the frames to stdout
+pub fn dump(config: &config::Config) -> std::io::Result<()> {
+    let (columns, rows) = size(config);
+    let mut matrix = Matrix::new(rows, columns, config.into());
+    let mut stdout = std::io::BufWriter::new(std::io::stdout().lock());
+
+    let format = |buffer: &Buffer| match config.headless_format {
+        config::HeadlessFormat::Text => export::to_text(buffer),
+        config::HeadlessFormat::Ansi => export::to_ansi(buffer, config.color_mode),
+    };
+
+    if config.leave_trail {
+        matrix.populate_background();
+    }
+    for frame in 1..=config.frames {
+        matrix.step(1.0 / config.fps.max(1) as f32);
+
+        // Print every frame, or just the last one if requested
+        if !config.last_frame || frame == config.frames {
+            if frame > 1 && !config.last_frame {
+                writeln!(stdout)?;
+            }
+            stdout.write_all(format(matrix.buffer()).as_bytes())?;
+        }
+    }
+
+    stdout.flush()
+}
+
+/// Runs the Matrix simulation without a terminal, and exports the frames as an animated GIF
+pub fn export_gif(config: &config::Config, path: &std::path::Path) -> std::io::Result<()> {
+    let (columns, rows) = size(config);
+    let file = std::fs::File::create(path)
+        .map_err(|e| std::io::Error::new(e.kind(), format!("{}: {e}", path.display())))?;
+
+    let mut matrix = Matrix::new(rows, columns, config.into());
+    let mut exporter = GifExporter::new(std::io::BufWriter::new(file), rows, columns, config.fps)?;
+
+    if config.leave_trail {
+        matrix.populate_background();
+    }
+    for _ in 0..config.frames {
+        matrix.step(exporter.frame_duration());
+        exporter.add_frame(matrix.buffer())?;
+    }
+
+    Ok(())
+}
+
+/// Runs the Matrix simulation without a terminal, and exports the frames as an animated SVG
+pub fn export_svg(config: &config::Config, path: &std::path::Path) -> std::io::Result<()> {
+    let (columns, rows) = size(config);
+    let file = std::fs::File::create(path)
+        .map_err(|e| std::io::Error::new(e.kind(), format!("{}: {e}", path.display())))?;
+
+    let mut matrix = Matrix::new(rows, columns, config.into());
+    let mut exporter = SvgExporter::new(rows, columns, config.fps);
+
+    if config.leave_trail {
+        matrix.populate_background();
+    }
+    for _ in 0..config.frames {
+        matrix.step(exporter.frame_duration());
+        exporter.add_frame(matrix.buffer());
+    }
+
+    exporter.finish(std::io::BufWriter::new(file))
+}
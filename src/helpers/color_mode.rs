@@ -0,0 +1,116 @@
+/// Describes how many colors the terminal can display
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorMode {
+    /// Detect the color mode from the environment
+    #[default]
+    Auto,
+    /// 24-bit RGB colors
+    TrueColor,
+    /// The xterm 256 color palette
+    Ansi256,
+    /// The 16 basic ANSI colors
+    Ansi16,
+    /// No colors at all
+    None,
+}
+
+impl ColorMode {
+    /// Resolves [ColorMode::Auto] by detecting the color mode from the environment.
+    /// Any other color mode is returned as is.
+    pub fn resolve(self) -> Self {
+        match self {
+            Self::Auto => Self::detect(
+                std::env::var("NO_COLOR").ok().as_deref(),
+                std::env::var("COLORTERM").ok().as_deref(),
+                std::env::var("TERM").ok().as_deref(),
+            ),
+            mode => mode,
+        }
+    }
+
+    /// Detects the color mode from the values of the `NO_COLOR`, `COLORTERM` and `TERM` environment variables
+    fn detect(no_color: Option<&str>, colorterm: Option<&str>, term: Option<&str>) -> Self {
+        // See https://no-color.org
+        if no_color.is_some_and(|v| !v.is_empty()) {
+            return Self::None;
+        }
+
+        if let Some("truecolor" | "24bit") = colorterm {
+            return Self::TrueColor;
+        }
+
+        match term {
+            Some("dumb") => Self::None,
+            Some("linux") => Self::Ansi16,
+            Some(t) if t.contains("truecolor") || t.contains("direct") => Self::TrueColor,
+            Some(t) if t.contains("256color") => Self::Ansi256,
+            Some(_) => Self::Ansi16,
+            // Windows terminals don't set TERM, but support 24-bit colors
+            None if cfg!(windows) => Self::TrueColor,
+            None => Self::Ansi16,
+        }
+    }
+}
+
+impl std::str::FromStr for ColorMode {
+    type Err = ParseColorModeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "truecolor" | "24bit" | "rgb" => Ok(Self::TrueColor),
+            "256" | "ansi256" => Ok(Self::Ansi256),
+            "16" | "ansi16" | "ansi" => Ok(Self::Ansi16),
+            "none" | "no" | "off" => Ok(Self::None),
+            _ => Err(ParseColorModeError::from(s.to_string())),
+        }
+    }
+}
+
+// ERROR
+// -----
+
+#[derive(Debug)]
+pub struct ParseColorModeError {
+    value: String,
+}
+
+impl From<String> for ParseColorModeError {
+    fn from(value: String) -> Self {
+        Self { value }
+    }
+}
+
+impl std::fmt::Display for ParseColorModeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unsupported color mode: {}", self.value)
+    }
+}
+
+impl std::error::Error for ParseColorModeError {}
+
+// -----
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_detect_color_mode_from_environment() {
+        let detect = ColorMode::detect;
+        assert_eq!(
+            detect(None, Some("truecolor"), Some("xterm")),
+            ColorMode::TrueColor
+        );
+        assert_eq!(
+            detect(None, None, Some("xterm-256color")),
+            ColorMode::Ansi256
+        );
+        assert_eq!(detect(None, None, Some("linux")), ColorMode::Ansi16);
+        assert_eq!(detect(None, None, Some("dumb")), ColorMode::None);
+        assert_eq!(detect(Some("1"), Some("truecolor"), None), ColorMode::None);
+        assert_eq!(detect(Some(""), Some("24bit"), None), ColorMode::TrueColor);
+    }
+}
@@ -1,11 +1,13 @@
 use std::str::FromStr;
 
+use super::named_colors;
+
 // ---------
 // RGB COLOR
 // ---------
 
 /// Holds the RGBColor values for a color
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct RGBColor(pub u8, pub u8, pub u8);
 
 impl RGBColor {
@@ -23,6 +25,73 @@ impl RGBColor {
     }
 }
 
+/// The levels of each channel in the 6x6x6 color cube of the xterm 256 color palette
+const ANSI256_CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The 16 basic ANSI colors, as displayed by xterm
+const ANSI16_PALETTE: [RGBColor; 16] = [
+    RGBColor(0, 0, 0),
+    RGBColor(128, 0, 0),
+    RGBColor(0, 128, 0),
+    RGBColor(128, 128, 0),
+    RGBColor(0, 0, 128),
+    RGBColor(128, 0, 128),
+    RGBColor(0, 128, 128),
+    RGBColor(192, 192, 192),
+    RGBColor(128, 128, 128),
+    RGBColor(255, 0, 0),
+    RGBColor(0, 255, 0),
+    RGBColor(255, 255, 0),
+    RGBColor(0, 0, 255),
+    RGBColor(255, 0, 255),
+    RGBColor(0, 255, 255),
+    RGBColor(255, 255, 255),
+];
+
+impl RGBColor {
+    /// Returns the squared euclidean distance to another [RGBColor]
+    fn distance(&self, other: &RGBColor) -> u32 {
+        let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+        d(self.r(), other.r()) + d(self.g(), other.g()) + d(self.b(), other.b())
+    }
+
+    /// Returns the index of the nearest color in the xterm 256 color palette.
+    /// Considers both the 6x6x6 color cube (16-231) and the grayscale ramp (232-255).
+    pub fn to_ansi256(&self) -> u8 {
+        // The nearest color in the color cube
+        let level = |v: u8| {
+            (0..6)
+                .min_by_key(|&i| (ANSI256_CUBE_LEVELS[i] as i32 - v as i32).abs())
+                .unwrap_or(0)
+        };
+        let (r, g, b) = (level(self.r()), level(self.g()), level(self.b()));
+        let cube = RGBColor(
+            ANSI256_CUBE_LEVELS[r],
+            ANSI256_CUBE_LEVELS[g],
+            ANSI256_CUBE_LEVELS[b],
+        );
+
+        // The nearest shade of gray in the grayscale ramp
+        let average = (self.r() as u32 + self.g() as u32 + self.b() as u32) / 3;
+        let step = (average.saturating_sub(3) / 10).min(23) as u8;
+        let gray_level = 8 + step * 10;
+        let gray = RGBColor(gray_level, gray_level, gray_level);
+
+        if self.distance(&gray) < self.distance(&cube) {
+            232 + step
+        } else {
+            16 + (36 * r + 6 * g + b) as u8
+        }
+    }
+
+    /// Returns the index of the nearest of the 16 basic ANSI colors
+    pub fn to_ansi16(&self) -> u8 {
+        (0..16)
+            .min_by_key(|&i| self.distance(&ANSI16_PALETTE[i as usize]))
+            .unwrap_or(0)
+    }
+}
+
 /// Parse a [RGBColor] from a tuple of (u8, u8, u8)
 impl From<(u8, u8, u8)> for RGBColor {
     fn from(value: (u8, u8, u8)) -> Self {
@@ -34,25 +103,45 @@ impl From<(u8, u8, u8)> for RGBColor {
 impl FromStr for RGBColor {
     type Err = ParseErrorKind;
 
+    /// Parses a color in any of the supported formats:
+    /// - `#rrggbb` or `#rgb` hex-colors
+    /// - `r,g,b` values
+    /// - `rgb(r, g, b)`, `hsl(h, s%, l%)` and `hsv(h, s%, v%)` functions, like in CSS
+    /// - CSS named colors (e.g. `lime`, `rebeccapurple`)
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
         if s.starts_with('#') {
-            return RGBColor::from_hex_str(s);
+            RGBColor::from_hex_str(s)
+        } else if s.ends_with(')') {
+            RGBColor::from_function_str(s)
         } else if s.contains(',') {
-            return RGBColor::from_rgb_str(s);
+            RGBColor::from_rgb_str(s)
         } else {
-            return RGBColor::from_named_color(s);
+            RGBColor::from_named_color(s)
         }
     }
 }
 
 impl RGBColor {
-    /// Parses a hex-color into a [RGBColor] value
+    /// Parses a hex-color (`#rrggbb` or the short `#rgb`) into a [RGBColor] value
     fn from_hex_str(s: &str) -> Result<Self, ParseErrorKind> {
         let color = s.trim_start_matches('#');
-        let r = u8::from_str_radix(&color[0..2], 16).map_err(ParseErrorKind::InvalidHexValue)?;
-        let g = u8::from_str_radix(&color[2..4], 16).map_err(ParseErrorKind::InvalidHexValue)?;
-        let b = u8::from_str_radix(&color[4..6], 16).map_err(ParseErrorKind::InvalidHexValue)?;
-        Ok(Self(r, g, b))
+        let hex =
+            |digits: &str| u8::from_str_radix(digits, 16).map_err(ParseErrorKind::InvalidHexValue);
+        match color.len() {
+            6 if color.is_ascii() => Ok(Self(
+                hex(&color[0..2])?,
+                hex(&color[2..4])?,
+                hex(&color[4..6])?,
+            )),
+            // Each digit is repeated, so `#0f8` is short for `#00ff88`
+            3 if color.is_ascii() => Ok(Self(
+                hex(&color[0..1])? * 17,
+                hex(&color[1..2])? * 17,
+                hex(&color[2..3])? * 17,
+            )),
+            _ => Err(ParseErrorKind::InvalidFormat(s.to_string())),
+        }
     }
 
     /// Parses a rgb color into a [RGBColor] value
@@ -60,29 +149,85 @@ impl RGBColor {
         let parts: Vec<&str> = s.split(',').map(|part| part.trim()).collect();
         if parts.len() == 3 {
             if let (Ok(r), Ok(g), Ok(b)) = (parts[0].parse(), parts[1].parse(), parts[2].parse()) {
-                return Ok(Self(r, g, b));
+                Ok(Self(r, g, b))
             } else {
-                return Err(ParseErrorKind::InvalidFormat(s.to_string()));
+                Err(ParseErrorKind::InvalidFormat(s.to_string()))
             }
         } else {
             Err(ParseErrorKind::InvalidFormat(s.to_string()))
         }
     }
 
-    /// Parses a named-color into a [RGBColor] value
-    fn from_named_color(s: &str) -> Result<Self, ParseErrorKind> {
-        match s.to_lowercase().as_str() {
-            "black" => Ok(RGBColor(0, 0, 0)),
-            "red" => Ok(RGBColor(255, 0, 0)),
-            "green" => Ok(RGBColor(0, 255, 0)),
-            "yellow" => Ok(RGBColor(255, 255, 0)),
-            "blue" => Ok(RGBColor(0, 0, 255)),
-            "magenta" => Ok(RGBColor(255, 0, 255)),
-            "cyan" => Ok(RGBColor(0, 255, 255)),
-            "white" => Ok(RGBColor(255, 255, 255)),
-            name => Err(ParseErrorKind::UnsupportedName(name.to_string())),
+    /// Parses a CSS color function (`rgb()`, `hsl()` or `hsv()`) into a [RGBColor] value.
+    /// The arguments can be separated by commas or spaces, and an alpha value is ignored.
+    fn from_function_str(s: &str) -> Result<Self, ParseErrorKind> {
+        let invalid = || ParseErrorKind::InvalidFormat(s.to_string());
+        let (name, args) = s
+            .strip_suffix(')')
+            .and_then(|s| s.split_once('('))
+            .ok_or_else(invalid)?;
+
+        // Drop the alpha value, given either after a slash or as the fourth argument
+        let args = args.split('/').next().unwrap_or_default();
+        let args: Vec<&str> = args
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|arg| !arg.is_empty())
+            .collect();
+        let args = match args.len() {
+            3 | 4 => &args[..3],
+            _ => return Err(invalid()),
+        };
+
+        match name.trim().to_lowercase().as_str() {
+            "rgb" | "rgba" => {
+                let channel = |arg: &str| parse_component(arg, 255.0).map(|v| v.round() as u8);
+                Ok(Self(
+                    channel(args[0])?,
+                    channel(args[1])?,
+                    channel(args[2])?,
+                ))
+            }
+            "hsl" | "hsla" => Ok(Self::from_hsl(
+                parse_hue(args[0])?,
+                parse_component(args[1], 100.0)? / 100.0,
+                parse_component(args[2], 100.0)? / 100.0,
+            )),
+            "hsv" | "hsva" | "hsb" => Ok(Self::from_hsv(
+                parse_hue(args[0])?,
+                parse_component(args[1], 100.0)? / 100.0,
+                parse_component(args[2], 100.0)? / 100.0,
+            )),
+            _ => Err(ParseErrorKind::UnsupportedName(name.trim().to_string())),
         }
     }
+
+    /// Parses a CSS named-color into a [RGBColor] value
+    fn from_named_color(s: &str) -> Result<Self, ParseErrorKind> {
+        let name = s.to_lowercase();
+        named_colors::lookup(&name).ok_or(ParseErrorKind::UnsupportedName(name))
+    }
+}
+
+/// Parses a color component, given either as a number between 0 and `max`, or as a percentage
+fn parse_component(s: &str, max: f32) -> Result<f32, ParseErrorKind> {
+    let value = match s.strip_suffix('%') {
+        Some(percentage) => percentage.parse::<f32>().map(|p| p / 100.0 * max),
+        None => s.parse::<f32>(),
+    };
+    match value {
+        Ok(v) if (0.0..=max).contains(&v) => Ok(v),
+        _ => Err(ParseErrorKind::InvalidComponent(s.to_string())),
+    }
+}
+
+/// Parses a hue in degrees, optionally suffixed with `deg`
+fn parse_hue(s: &str) -> Result<f32, ParseErrorKind> {
+    s.strip_suffix("deg")
+        .unwrap_or(s)
+        .parse::<f32>()
+        .ok()
+        .filter(|h| h.is_finite())
+        .ok_or_else(|| ParseErrorKind::InvalidComponent(s.to_string()))
 }
 
 impl std::ops::Mul<f32> for RGBColor {
@@ -90,20 +235,221 @@ impl std::ops::Mul<f32> for RGBColor {
 
     fn mul(self, rhs: f32) -> Self::Output {
         RGBColor(
-            (self.r() as f32 * rhs).min(255.0).max(0.0) as u8,
-            (self.g() as f32 * rhs).min(255.0).max(0.0) as u8,
-            (self.b() as f32 * rhs).min(255.0).max(0.0) as u8,
+            (self.r() as f32 * rhs).clamp(0.0, 255.0) as u8,
+            (self.g() as f32 * rhs).clamp(0.0, 255.0) as u8,
+            (self.b() as f32 * rhs).clamp(0.0, 255.0) as u8,
         )
     }
 }
 
+// -------------
+// COLOR SPACES
+// -------------
+
+/// Converts an sRGB channel value into linear light, between 0 and 1
+fn to_linear(value: u8) -> f32 {
+    let v = value as f32 / 255.0;
+    if v <= 0.04045 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Converts a linear light value, between 0 and 1, back into an sRGB channel value
+fn from_linear(value: f32) -> u8 {
+    let v = value.clamp(0.0, 1.0);
+    let v = if v <= 0.0031308 {
+        v * 12.92
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    };
+    (v * 255.0).round() as u8
+}
+
+impl RGBColor {
+    /// Returns the hue (in degrees from 0 to 360), saturation and lightness (from 0 to 1) of the color
+    pub fn to_hsl(&self) -> (f32, f32, f32) {
+        let (r, g, b) = (
+            self.r() as f32 / 255.0,
+            self.g() as f32 / 255.0,
+            self.b() as f32 / 255.0,
+        );
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let l = (max + min) / 2.0;
+        let d = max - min;
+        if d == 0.0 {
+            return (0.0, 0.0, l);
+        }
+
+        let s = d / (1.0 - (2.0 * l - 1.0).abs());
+        let h = if max == r {
+            60.0 * ((g - b) / d).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / d + 2.0)
+        } else {
+            60.0 * ((r - g) / d + 4.0)
+        };
+        (h, s, l)
+    }
+
+    /// Constructs a [RGBColor] from the hue (in degrees), saturation and lightness (from 0 to 1)
+    pub fn from_hsl(h: f32, s: f32, l: f32) -> Self {
+        let (s, l) = (s.clamp(0.0, 1.0), l.clamp(0.0, 1.0));
+        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let h = h.rem_euclid(360.0) / 60.0;
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u8 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        let m = l - c / 2.0;
+        let channel = |v: f32| ((v + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+        Self(channel(r), channel(g), channel(b))
+    }
+
+    /// Returns the color with its hue rotated by the given number of degrees
+    pub fn rotate_hue(&self, degrees: f32) -> Self {
+        let (h, s, l) = self.to_hsl();
+        Self::from_hsl(h + degrees, s, l)
+    }
+
+    /// Constructs a [RGBColor] from the hue (in degrees), saturation and value (from 0 to 1)
+    pub fn from_hsv(h: f32, s: f32, v: f32) -> Self {
+        let (s, v) = (s.clamp(0.0, 1.0), v.clamp(0.0, 1.0));
+        // Convert to HSL, which shares the hue
+        let l = v * (1.0 - s / 2.0);
+        let s = if l == 0.0 || l == 1.0 {
+            0.0
+        } else {
+            (v - l) / l.min(1.0 - l)
+        };
+        Self::from_hsl(h, s, l)
+    }
+
+    /// Returns the color in the OKLab color space, as (lightness, a, b)
+    fn to_oklab(self) -> (f32, f32, f32) {
+        let (r, g, b) = (
+            to_linear(self.r()),
+            to_linear(self.g()),
+            to_linear(self.b()),
+        );
+        let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+        let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+        let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+        (
+            0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+            1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+            0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+        )
+    }
+
+    /// Constructs a [RGBColor] from the OKLab color space
+    fn from_oklab(lightness: f32, a: f32, b: f32) -> Self {
+        let l = (lightness + 0.396_337_78 * a + 0.215_803_76 * b).powi(3);
+        let m = (lightness - 0.105_561_346 * a - 0.063_854_17 * b).powi(3);
+        let s = (lightness - 0.089_484_18 * a - 1.291_485_5 * b).powi(3);
+        Self(
+            from_linear(4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s),
+            from_linear(-1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s),
+            from_linear(-0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s),
+        )
+    }
+}
+
+/// The color space that [gradients](LinearGradient) interpolate in
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interpolation {
+    /// Interpolates the raw sRGB values
+    #[default]
+    Srgb,
+    /// Interpolates in linear light, which keeps the mix of two colors from getting too dark
+    LinearRgb,
+    /// Interpolates the hue, saturation and lightness, taking the shortest way around the hue circle
+    Hsl,
+    /// Interpolates in the perceptually uniform OKLab color space
+    Oklab,
+}
+
+impl Interpolation {
+    /// Mixes the two colors in this color space. A factor of 0 gives `start`, and 1 gives `end`
+    pub fn mix(&self, start: RGBColor, end: RGBColor, factor: f32) -> RGBColor {
+        let lerp = |a: f32, b: f32| a + factor * (b - a);
+        match self {
+            Self::Srgb => {
+                let channel = |a: u8, b: u8| lerp(a as f32, b as f32).round() as u8;
+                RGBColor(
+                    channel(start.r(), end.r()),
+                    channel(start.g(), end.g()),
+                    channel(start.b(), end.b()),
+                )
+            }
+            Self::LinearRgb => {
+                let channel = |a: u8, b: u8| from_linear(lerp(to_linear(a), to_linear(b)));
+                RGBColor(
+                    channel(start.r(), end.r()),
+                    channel(start.g(), end.g()),
+                    channel(start.b(), end.b()),
+                )
+            }
+            Self::Hsl => {
+                let (mut h1, s1, l1) = start.to_hsl();
+                let (mut h2, s2, l2) = end.to_hsl();
+                // Grays have no hue of their own, so they take on the hue of the other color
+                if s1 == 0.0 {
+                    h1 = h2;
+                } else if s2 == 0.0 {
+                    h2 = h1;
+                }
+                // Take the shortest way around the hue circle
+                let mut dh = h2 - h1;
+                if dh > 180.0 {
+                    dh -= 360.0;
+                } else if dh < -180.0 {
+                    dh += 360.0;
+                }
+                RGBColor::from_hsl(h1 + factor * dh, lerp(s1, s2), lerp(l1, l2))
+            }
+            Self::Oklab => {
+                let (l1, a1, b1) = start.to_oklab();
+                let (l2, a2, b2) = end.to_oklab();
+                RGBColor::from_oklab(lerp(l1, l2), lerp(a1, a2), lerp(b1, b2))
+            }
+        }
+    }
+}
+
+impl FromStr for Interpolation {
+    type Err = ParseErrorKind;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "srgb" | "rgb" => Ok(Self::Srgb),
+            "linear-rgb" | "linear" | "linear-srgb" => Ok(Self::LinearRgb),
+            "hsl" => Ok(Self::Hsl),
+            "oklab" => Ok(Self::Oklab),
+            name => Err(ParseErrorKind::UnsupportedName(name.to_string())),
+        }
+    }
+}
+
 // ---------
 // GRADIENTS
 // ---------
 
+/// A gradient that linearly interpolates between any number of color stops.
+///
+/// Each stop is a color at a position between 0 and 1, sorted by position.
+#[derive(Clone, Debug, PartialEq)]
 pub struct LinearGradient {
-    start: RGBColor,
-    end: RGBColor,
+    stops: Vec<(f32, RGBColor)>,
+    /// The color space the stops are interpolated in
+    interpolation: Interpolation,
 }
 
 pub struct LinearGradientSteps<'a> {
@@ -112,12 +458,12 @@ pub struct LinearGradientSteps<'a> {
     count: usize,
 }
 
-impl<'a> Iterator for LinearGradientSteps<'a> {
+impl Iterator for LinearGradientSteps<'_> {
     type Item = RGBColor;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.current < self.count {
-            let factor = self.current as f32 / (self.count - 1) as f32;
+            let factor = self.current as f32 / (self.count - 1).max(1) as f32;
             self.current += 1;
             Some(self.gradient.interpolate(factor))
         } else {
@@ -127,40 +473,197 @@ impl<'a> Iterator for LinearGradientSteps<'a> {
 }
 
 impl LinearGradient {
-    /// Instantiate a new linear gradient
+    /// Instantiate a new linear gradient between two colors
     pub fn new(start: RGBColor, end: RGBColor) -> Self {
-        Self { start, end }
+        Self {
+            stops: vec![(0.0, start), (1.0, end)],
+            interpolation: Interpolation::default(),
+        }
     }
 
-    pub fn delta(&self) -> (i16, i16, i16) {
-        (
-            self.end.r() as i16 - self.start.r() as i16,
-            self.end.g() as i16 - self.start.g() as i16,
-            self.end.b() as i16 - self.start.b() as i16,
-        )
+    /// Instantiate a new linear gradient from the given color stops.
+    /// The positions are clamped between 0 and 1, and the stops are sorted by position.
+    pub fn with_stops(stops: Vec<(f32, RGBColor)>) -> Self {
+        let mut stops: Vec<_> = stops
+            .into_iter()
+            .map(|(position, color)| (position.clamp(0.0, 1.0), color))
+            .collect();
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self {
+            stops,
+            interpolation: Interpolation::default(),
+        }
+    }
+
+    /// Returns the built-in gradient preset with the given name, if there is one.
+    ///
+    /// Available presets: `fire`, `ice` and `sunset`.
+    pub fn preset(name: &str) -> Option<Self> {
+        let stops = match name.to_lowercase().as_str() {
+            "fire" => vec![
+                (0.0, RGBColor(255, 255, 180)),
+                (0.2, RGBColor(255, 200, 0)),
+                (0.5, RGBColor(255, 80, 0)),
+                (1.0, RGBColor(80, 0, 0)),
+            ],
+            "ice" => vec![
+                (0.0, RGBColor(255, 255, 255)),
+                (0.3, RGBColor(120, 220, 255)),
+                (1.0, RGBColor(0, 40, 110)),
+            ],
+            "sunset" => vec![
+                (0.0, RGBColor(255, 220, 120)),
+                (0.3, RGBColor(255, 100, 60)),
+                (0.6, RGBColor(200, 40, 120)),
+                (1.0, RGBColor(40, 10, 80)),
+            ],
+            _ => return None,
+        };
+        Some(Self::with_stops(stops))
     }
 
-    /// Interpolate between two colors. The factor has to be between 0 and 1
+    /// Sets the color space the stops are interpolated in
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Returns the color stops of the gradient, as (position, color) pairs
+    pub fn stops(&self) -> &[(f32, RGBColor)] {
+        &self.stops
+    }
+
+    /// Interpolate between the color stops. The factor has to be between 0 and 1
     pub fn interpolate(&self, factor: f32) -> RGBColor {
         assert!(
-            factor >= 0.0 && factor <= 1.0,
+            (0.0..=1.0).contains(&factor),
             "The factor value must be between 0 and 1"
         );
-        let delta = self.delta();
-        let r = self.start.r() as f32 + factor * delta.0 as f32;
-        let g = self.start.g() as f32 + factor * delta.1 as f32;
-        let b = self.start.b() as f32 + factor * delta.2 as f32;
-        RGBColor(r.round() as u8, g.round() as u8, b.round() as u8)
-    }
-
-    // /// Returns an iterator over each color stop
-    // pub fn steps(&self, count: usize) -> LinearGradientSteps {
-    //     LinearGradientSteps {
-    //         gradient: self,
-    //         current: 0,
-    //         count,
-    //     }
-    // }
+
+        // Find the pair of stops surrounding the factor
+        let end = self
+            .stops
+            .iter()
+            .position(|(position, _)| *position >= factor)
+            .unwrap_or(self.stops.len() - 1);
+        let (end_position, end_color) = self.stops[end];
+        let (start_position, start_color) = self.stops[end.saturating_sub(1)];
+
+        // Before the first stop or after the last one, the color is solid
+        let span = end_position - start_position;
+        if span <= 0.0 || factor > end_position {
+            return if factor < start_position {
+                start_color
+            } else {
+                end_color
+            };
+        }
+
+        let t = (factor - start_position) / span;
+        self.interpolation.mix(start_color, end_color, t)
+    }
+
+    /// Returns an iterator over `count` evenly spaced colors along the gradient
+    pub fn steps(&self, count: usize) -> LinearGradientSteps<'_> {
+        LinearGradientSteps {
+            gradient: self,
+            current: 0,
+            count,
+        }
+    }
+}
+
+/// Parses a gradient from a comma-separated list of color stops (e.g. `#fff,#0f0@0.2,#003300`),
+/// or the name of a [preset](LinearGradient::preset).
+///
+/// Each stop is a color, optionally followed by `@` and its position, either as
+/// a number between 0 and 1 or a percentage (e.g. `#0f0@20%`). Stops without a position
+/// are spaced evenly between their neighbours, with the first and last defaulting to 0 and 1.
+impl FromStr for LinearGradient {
+    type Err = ParseErrorKind;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(preset) = Self::preset(s.trim()) {
+            return Ok(preset);
+        }
+
+        let mut colors = Vec::new();
+        let mut positions = Vec::new();
+        for stop in split_stops(s) {
+            let (color, position) = match stop.rsplit_once('@') {
+                Some((color, position)) => (color, Some(parse_position(position)?)),
+                None => (stop, None),
+            };
+            colors.push(color.trim().parse::<RGBColor>()?);
+            positions.push(position);
+        }
+        if colors.len() < 2 {
+            return Err(ParseErrorKind::InvalidGradient(s.to_string()));
+        }
+
+        // Fill in the missing positions
+        let last = positions.len() - 1;
+        positions[0] = positions[0].or(Some(0.0));
+        positions[last] = positions[last].or(Some(1.0));
+        let mut i = 0;
+        while i < last {
+            // The stops between two known positions are spaced evenly between them
+            let next = (i + 1..=last)
+                .find(|&j| positions[j].is_some())
+                .unwrap_or(last);
+            let (from, to) = (positions[i].unwrap_or(0.0), positions[next].unwrap_or(1.0));
+            let count = (next - i) as f32;
+            for (j, position) in positions[i + 1..next].iter_mut().enumerate() {
+                *position = Some(from + (to - from) * (j + 1) as f32 / count);
+            }
+            i = next;
+        }
+
+        // Positions can't go backwards, like in CSS
+        let mut previous = 0.0_f32;
+        let stops = positions
+            .into_iter()
+            .zip(colors)
+            .map(|(position, color)| {
+                previous = previous.max(position.unwrap_or(previous));
+                (previous, color)
+            })
+            .collect();
+        Ok(Self::with_stops(stops))
+    }
+}
+
+/// Splits the gradient stops on the commas that aren't inside parentheses
+fn split_stops(s: &str) -> Vec<&str> {
+    let mut stops = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                stops.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    stops.push(&s[start..]);
+    stops
+}
+
+/// Parses the position of a gradient stop, given as a number between 0 and 1 or a percentage
+fn parse_position(s: &str) -> Result<f32, ParseErrorKind> {
+    let s = s.trim();
+    let position = match s.strip_suffix('%') {
+        Some(percentage) => percentage.trim().parse::<f32>().map(|p| p / 100.0),
+        None => s.parse::<f32>(),
+    };
+    match position {
+        Ok(p) if (0.0..=1.0).contains(&p) => Ok(p),
+        _ => Err(ParseErrorKind::InvalidGradient(s.to_string())),
+    }
 }
 
 // ------
@@ -176,6 +679,10 @@ pub enum ParseErrorKind {
     UnsupportedName(String),
     /// Failed to parse hex value
     InvalidHexValue(std::num::ParseIntError),
+    /// A component of the color was not a number, or out of range
+    InvalidComponent(String),
+    /// The gradient needs at least two stops, with positions between 0 and 1
+    InvalidGradient(String),
 }
 
 impl std::fmt::Display for ParseErrorKind {
@@ -184,6 +691,10 @@ impl std::fmt::Display for ParseErrorKind {
             ParseErrorKind::InvalidFormat(format) => write!(f, "Invalid color format: {format}"),
             ParseErrorKind::UnsupportedName(name) => write!(f, "Unsupported color name: {name}"),
             ParseErrorKind::InvalidHexValue(v) => write!(f, "Invalid hex value: {v}"),
+            ParseErrorKind::InvalidComponent(component) => {
+                write!(f, "Invalid color component: {component}")
+            }
+            ParseErrorKind::InvalidGradient(gradient) => write!(f, "Invalid gradient: {gradient}"),
         }
     }
 }
@@ -224,11 +735,14 @@ mod tests {
         assert_eq!(RGBColor::from_hex_str("#FF0000"), Ok(RGBColor(255, 0, 0)));
         assert_eq!(RGBColor::from_hex_str("#00FF00"), Ok(RGBColor(0, 255, 0)));
         assert_eq!(RGBColor::from_hex_str("#0000FF"), Ok(RGBColor(0, 0, 255)));
+        assert_eq!(RGBColor::from_hex_str("#0f8"), Ok(RGBColor(0, 255, 136)));
+        assert_eq!(
+            RGBColor::from_hex_str("#0f"),
+            Err(ParseErrorKind::InvalidFormat("#0f".into()))
+        );
         assert!(
-            RGBColor::from_hex_str("#GGGGGG").is_err_and(|x| match x {
-                ParseErrorKind::InvalidHexValue(_) => true,
-                _ => false,
-            }),
+            RGBColor::from_hex_str("#GGGGGG")
+                .is_err_and(|x| matches!(x, ParseErrorKind::InvalidHexValue(_))),
             "Invalid Hex Format"
         )
     }
@@ -269,16 +783,170 @@ mod tests {
             RGBColor::from_named_color("white"),
             Ok(RGBColor(255, 255, 255))
         );
+        assert_eq!(
+            RGBColor::from_named_color("RebeccaPurple"),
+            Ok(RGBColor(102, 51, 153))
+        );
+        assert_eq!(RGBColor::from_named_color("lime"), Ok(RGBColor(0, 255, 0)));
         assert_eq!(
             RGBColor::from_named_color("unknown"),
             Err(ParseErrorKind::UnsupportedName("unknown".into()))
         );
     }
 
+    #[test]
+    fn should_parse_css_color_functions() {
+        assert_eq!("rgb(0, 255, 70)".parse(), Ok(RGBColor(0, 255, 70)));
+        assert_eq!("rgb(0 100% 50% / 0.5)".parse(), Ok(RGBColor(0, 255, 128)));
+        assert_eq!("rgba(255,0,0,0.3)".parse(), Ok(RGBColor(255, 0, 0)));
+        assert_eq!("hsl(120, 100%, 25%)".parse(), Ok(RGBColor(0, 128, 0)));
+        assert_eq!("HSL(240deg 100% 50%)".parse(), Ok(RGBColor(0, 0, 255)));
+        assert_eq!("hsv(60, 100%, 100%)".parse(), Ok(RGBColor(255, 255, 0)));
+        assert_eq!("hsv(0, 0%, 50%)".parse(), Ok(RGBColor(128, 128, 128)));
+
+        assert_eq!(
+            RGBColor::from_str("rgb(300, 0, 0)"),
+            Err(ParseErrorKind::InvalidComponent("300".into()))
+        );
+        assert_eq!(
+            RGBColor::from_str("hsl(x, 10%, 10%)"),
+            Err(ParseErrorKind::InvalidComponent("x".into()))
+        );
+        assert_eq!(
+            RGBColor::from_str("rgb(1, 2)"),
+            Err(ParseErrorKind::InvalidFormat("rgb(1, 2)".into()))
+        );
+        assert_eq!(
+            RGBColor::from_str("lab(1, 2, 3)"),
+            Err(ParseErrorKind::UnsupportedName("lab".into()))
+        );
+        assert!(RGBColor::from_str("#ア").is_err());
+    }
+
+    #[test]
+    fn should_quantize_to_the_nearest_ansi256_color() {
+        assert_eq!(RGBColor(0, 0, 0).to_ansi256(), 16);
+        assert_eq!(RGBColor(255, 255, 255).to_ansi256(), 231);
+        assert_eq!(RGBColor(0, 255, 70).to_ansi256(), 47);
+        assert_eq!(RGBColor(128, 128, 128).to_ansi256(), 244);
+    }
+
+    #[test]
+    fn should_quantize_to_the_nearest_ansi16_color() {
+        assert_eq!(RGBColor(0, 0, 0).to_ansi16(), 0);
+        assert_eq!(RGBColor(0, 255, 70).to_ansi16(), 10);
+        assert_eq!(RGBColor(0, 84, 23).to_ansi16(), 2);
+        assert_eq!(RGBColor(200, 255, 200).to_ansi16(), 7);
+    }
+
     #[test]
     fn should_perform_scalar_multiplication_with_overloaded_operator() {
         let color = RGBColor(50, 67, 150);
         let new_color = color * 2.0;
         assert_eq!(new_color, RGBColor(100, 134, 255));
     }
+
+    #[test]
+    fn should_interpolate_between_multiple_stops() {
+        let gradient = LinearGradient::with_stops(vec![
+            (0.0, RGBColor(255, 255, 255)),
+            (0.5, RGBColor(0, 255, 0)),
+            (1.0, RGBColor(0, 0, 0)),
+        ]);
+        assert_eq!(gradient.interpolate(0.0), RGBColor(255, 255, 255));
+        assert_eq!(gradient.interpolate(0.25), RGBColor(128, 255, 128));
+        assert_eq!(gradient.interpolate(0.5), RGBColor(0, 255, 0));
+        assert_eq!(gradient.interpolate(0.75), RGBColor(0, 128, 0));
+        assert_eq!(gradient.interpolate(1.0), RGBColor(0, 0, 0));
+
+        let steps: Vec<_> = gradient.steps(3).collect();
+        assert_eq!(
+            steps,
+            [
+                RGBColor(255, 255, 255),
+                RGBColor(0, 255, 0),
+                RGBColor(0, 0, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn should_parse_gradients_with_positioned_stops() {
+        let gradient: LinearGradient = "#fff,#0f0@0.2,#003300".parse().unwrap();
+        assert_eq!(
+            gradient.stops(),
+            [
+                (0.0, RGBColor(255, 255, 255)),
+                (0.2, RGBColor(0, 255, 0)),
+                (1.0, RGBColor(0, 51, 0))
+            ]
+        );
+
+        let gradient: LinearGradient = "red, yellow, blue@50%, black".parse().unwrap();
+        let positions: Vec<_> = gradient.stops().iter().map(|s| s.0).collect();
+        assert_eq!(positions, [0.0, 0.25, 0.5, 1.0]);
+        assert_eq!(gradient.interpolate(0.75), RGBColor(0, 0, 128));
+
+        assert!("fire".parse::<LinearGradient>().is_ok());
+        assert_eq!(
+            "#fff".parse::<LinearGradient>(),
+            Err(ParseErrorKind::InvalidGradient("#fff".into()))
+        );
+        assert_eq!(
+            "#fff,#000@2".parse::<LinearGradient>(),
+            Err(ParseErrorKind::InvalidGradient("2".into()))
+        );
+    }
+
+    #[test]
+    fn should_convert_to_and_from_hsl() {
+        assert_eq!(RGBColor(255, 0, 0).to_hsl(), (0.0, 1.0, 0.5));
+        assert_eq!(RGBColor(0, 0, 255).to_hsl(), (240.0, 1.0, 0.5));
+        assert_eq!(RGBColor::from_hsl(120.0, 1.0, 0.25), RGBColor(0, 128, 0));
+        let color = RGBColor(0, 255, 70);
+        let (h, s, l) = color.to_hsl();
+        assert_eq!(RGBColor::from_hsl(h, s, l), color);
+    }
+
+    #[test]
+    fn should_rotate_the_hue() {
+        assert_eq!(RGBColor(255, 0, 0).rotate_hue(120.0), RGBColor(0, 255, 0));
+        assert_eq!(RGBColor(255, 0, 0).rotate_hue(-120.0), RGBColor(0, 0, 255));
+        assert_eq!(RGBColor(0, 255, 70).rotate_hue(360.0), RGBColor(0, 255, 70));
+        assert_eq!(RGBColor(90, 90, 90).rotate_hue(90.0), RGBColor(90, 90, 90));
+    }
+
+    #[test]
+    fn should_interpolate_in_the_chosen_color_space() {
+        let (green, blue) = (RGBColor(0, 255, 0), RGBColor(0, 0, 255));
+        assert_eq!(
+            Interpolation::Srgb.mix(green, blue, 0.5),
+            RGBColor(0, 128, 128)
+        );
+        assert_eq!(
+            Interpolation::LinearRgb.mix(green, blue, 0.5),
+            RGBColor(0, 188, 188)
+        );
+        assert_eq!(
+            Interpolation::Hsl.mix(green, blue, 0.5),
+            RGBColor(0, 255, 255)
+        );
+        for interpolation in [
+            Interpolation::LinearRgb,
+            Interpolation::Hsl,
+            Interpolation::Oklab,
+        ] {
+            assert_eq!(interpolation.mix(green, blue, 0.0), green);
+            assert_eq!(interpolation.mix(green, blue, 1.0), blue);
+        }
+
+        // The OKLab midpoint is brighter than the muddy sRGB one
+        let oklab = Interpolation::Oklab.mix(green, blue, 0.5);
+        assert!(oklab.g() > 128 && oklab.b() > 128);
+
+        let gradient = LinearGradient::new(green, blue).with_interpolation(Interpolation::Hsl);
+        assert_eq!(gradient.interpolate(0.5), RGBColor(0, 255, 255));
+        assert_eq!("oklab".parse(), Ok(Interpolation::Oklab));
+        assert_eq!("linear-rgb".parse(), Ok(Interpolation::LinearRgb));
+    }
 }
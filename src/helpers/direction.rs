@@ -1,5 +1,11 @@
-/// Describes the direction of motion of the Matrix Streams
-#[derive(Default, Clone, Debug)]
+use rand::{distributions::WeightedIndex, prelude::Distribution, Rng};
+
+/// Describes the direction of motion of the Matrix Streams.
+///
+/// Besides the eight named directions, the streams can flow at any angle, in degrees.
+/// Angles follow the mathematical convention: 0° points right, 90° up, 180° left and 270° down.
+/// The streams can also each pick their own direction, from a weighted [mix](Direction::Mixed).
+#[derive(Default, Clone, Debug, PartialEq)]
 pub enum Direction {
     #[default]
     Down,
@@ -10,13 +16,144 @@ pub enum Direction {
     DiagonalLeftReverse,
     DiagonalRight,
     DiagonalRightReverse,
+    /// An arbitrary angle, in degrees
+    Degrees(f32),
+    /// A mix of directions, along with their weights. Each stream picks one of them at random,
+    /// every time it's regenerated, with a likelihood in proportion to its weight.
+    Mixed(Vec<(Direction, f32)>),
+}
+
+impl Direction {
+    /// Returns the angle of the direction, in degrees between 0 and 360
+    pub fn angle(&self) -> f32 {
+        match self {
+            Self::Right => 0.0,
+            Self::DiagonalLeftReverse => 45.0,
+            Self::Up => 90.0,
+            Self::DiagonalRightReverse => 135.0,
+            Self::Left => 180.0,
+            Self::DiagonalLeft => 225.0,
+            Self::Down => 270.0,
+            Self::DiagonalRight => 315.0,
+            Self::Degrees(degrees) => degrees.rem_euclid(360.0),
+            Self::Mixed(_) => self.primary().angle(),
+        }
+    }
+
+    /// Returns the most likely direction of a [mix](Direction::Mixed), or the direction itself.
+    /// The streams are laid out in lanes across this direction.
+    pub fn primary(&self) -> &Direction {
+        match self {
+            Self::Mixed(directions) => directions
+                .iter()
+                .rev() // So that the first of the most likely directions wins the ties
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+                .map_or(&Self::Down, |(direction, _)| direction),
+            direction => direction,
+        }
+    }
+
+    /// Picks a direction from the [mix](Direction::Mixed) at random, based on the weights.
+    /// Any other direction is returned as it is.
+    pub fn choose(&self, rng: &mut impl Rng) -> Direction {
+        match self {
+            Self::Mixed(directions) => WeightedIndex::new(directions.iter().map(|(_, w)| *w))
+                .map_or(Self::Down, |index| directions[index.sample(rng)].0.clone()),
+            direction => direction.clone(),
+        }
+    }
+
+    /// Returns the direction as an (x, y) vector on the screen, where y grows downwards.
+    ///
+    /// The vector is scaled so that its larger component is exactly one, which means a stream
+    /// moves one whole cell along its main axis per cell of travel. The diagonals are (±1, ±1).
+    pub fn vector(&self) -> (f32, f32) {
+        let radians = (self.angle() as f64).to_radians();
+        let (x, y) = (radians.cos(), -radians.sin());
+        let scale = x.abs().max(y.abs());
+        // Snap away the rounding errors, so that the right angles are exact
+        let snap = |v: f64| {
+            let v = v / scale;
+            if v.abs() < 1e-6 {
+                0.0
+            } else {
+                v as f32
+            }
+        };
+        (snap(x), snap(y))
+    }
+
+    /// Returns the next named direction, cycling back around to [Direction::Down].
+    /// Angles and mixed directions aren't part of the cycle, so they're followed by [Direction::Down].
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Down => Self::Up,
+            Self::Up => Self::Left,
+            Self::Left => Self::Right,
+            Self::Right => Self::DiagonalLeft,
+            Self::DiagonalLeft => Self::DiagonalLeftReverse,
+            Self::DiagonalLeftReverse => Self::DiagonalRight,
+            Self::DiagonalRight => Self::DiagonalRightReverse,
+            Self::DiagonalRightReverse | Self::Degrees(_) | Self::Mixed(_) => Self::Down,
+        }
+    }
+
+    /// Returns the next direction in a cycle that started from the `initial` direction.
+    /// An initial angle or mixed direction is slotted in after [Direction::DiagonalRightReverse],
+    /// so cycling through the directions comes back around to it.
+    pub fn next_from(&self, initial: &Direction) -> Self {
+        match self {
+            Self::DiagonalRightReverse if matches!(initial, Self::Degrees(_) | Self::Mixed(_)) => {
+                initial.clone()
+            }
+            _ => self.next(),
+        }
+    }
 }
 
 impl std::str::FromStr for Direction {
     type Err = ParseDirectionError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // A weighted list of directions, like "down:0.8,up:0.2"
+        if s.contains([',', ':']) {
+            let directions = s
+                .split(',')
+                .map(|entry| {
+                    let (direction, weight) = entry.split_once(':').unwrap_or((entry, "1"));
+                    let direction = direction.trim().parse::<Direction>()?;
+                    match weight.trim().parse::<f32>() {
+                        Ok(weight) if weight.is_finite() && weight >= 0.0 => {
+                            Ok((direction, weight))
+                        }
+                        _ => Err(ParseDirectionError::from(s.to_string())),
+                    }
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            // Mixes can't be nested, and at least one of the directions has to have some weight
+            let nested = directions.iter().any(|(d, _)| matches!(d, Self::Mixed(_)));
+            if nested || directions.iter().all(|(_, weight)| *weight == 0.0) {
+                return Err(ParseDirectionError::from(s.to_string()));
+            }
+            return Ok(Self::Mixed(directions));
+        }
+
         match s.to_lowercase().as_str() {
+            "random" | "mixed" => Ok(Self::Mixed(
+                [
+                    Self::Down,
+                    Self::Up,
+                    Self::Left,
+                    Self::Right,
+                    Self::DiagonalLeft,
+                    Self::DiagonalLeftReverse,
+                    Self::DiagonalRight,
+                    Self::DiagonalRightReverse,
+                ]
+                .map(|direction| (direction, 1.0))
+                .to_vec(),
+            )),
             "up" | "vertical-reverse" => Ok(Self::Up),
             "down" | "vertical" => Ok(Self::Down),
             "left" | "horizontal" => Ok(Self::Left),
@@ -25,7 +162,16 @@ impl std::str::FromStr for Direction {
             "diagonal-left-reverse" | "top-right" => Ok(Self::DiagonalLeftReverse),
             "diagonal-right" | "bottom-right" => Ok(Self::DiagonalRight),
             "diagonal-right-reverse" | "top-left" => Ok(Self::DiagonalRightReverse),
-            _ => Err(ParseDirectionError::from(s.to_string())),
+            name => {
+                // Otherwise, the direction is an angle in degrees (e.g. "300" or "300deg")
+                let degrees = name.trim_end_matches("deg").trim_end_matches('°').trim();
+                match degrees.parse::<f32>() {
+                    Ok(degrees) if degrees.is_finite() => {
+                        Ok(Self::Degrees(degrees.rem_euclid(360.0)))
+                    }
+                    _ => Err(ParseDirectionError::from(s.to_string())),
+                }
+            }
         }
     }
 }
@@ -51,3 +197,87 @@ impl std::fmt::Display for ParseDirectionError {
 }
 
 impl std::error::Error for ParseDirectionError {}
+
+// -----
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn should_parse_names_and_angles() {
+        assert_eq!("down".parse::<Direction>().unwrap().angle(), 270.0);
+        assert_eq!("top-left".parse::<Direction>().unwrap().angle(), 135.0);
+        assert_eq!(
+            "300".parse::<Direction>().unwrap(),
+            Direction::Degrees(300.0)
+        );
+        assert_eq!(
+            "-45deg".parse::<Direction>().unwrap(),
+            Direction::Degrees(315.0)
+        );
+        assert!("sideways".parse::<Direction>().is_err());
+        assert!("NaN".parse::<Direction>().is_err());
+    }
+
+    #[test]
+    fn should_parse_and_choose_from_mixed_directions() {
+        let mixed: Direction = "down:0.8, up:0.2".parse().unwrap();
+        assert_eq!(
+            mixed,
+            Direction::Mixed(vec![(Direction::Down, 0.8), (Direction::Up, 0.2)])
+        );
+        assert_eq!(mixed.primary(), &Direction::Down);
+        assert_eq!(mixed.angle(), 270.0);
+
+        let random: Direction = "random".parse().unwrap();
+        assert!(matches!(&random, Direction::Mixed(directions) if directions.len() == 8));
+        assert_eq!(
+            "left,300".parse::<Direction>().unwrap().primary(),
+            &Direction::Left
+        );
+
+        assert!("down:-1,up".parse::<Direction>().is_err());
+        assert!("down:0,up:0".parse::<Direction>().is_err());
+        assert!("down,random".parse::<Direction>().is_err());
+
+        // The directions are picked in proportion to their weights
+        let mut rng = StdRng::seed_from_u64(7);
+        let ups = (0..1000)
+            .filter(|_| mixed.choose(&mut rng) == Direction::Up)
+            .count();
+        assert!((150..=250).contains(&ups), "{ups}");
+    }
+
+    #[test]
+    fn should_cycle_back_around_to_the_initial_direction() {
+        let cycle = |initial: Direction| {
+            let mut direction = initial.next_from(&initial);
+            let mut steps = 1;
+            while direction != initial {
+                direction = direction.next_from(&initial);
+                steps += 1;
+            }
+            steps
+        };
+        assert_eq!(cycle(Direction::Left), 8);
+        assert_eq!(cycle(Direction::Degrees(300.0)), 9);
+        assert_eq!(cycle("down:0.8,up:0.2".parse().unwrap()), 9);
+        assert_eq!(Direction::Degrees(300.0).next(), Direction::Down);
+    }
+
+    #[test]
+    fn should_scale_the_vector_to_the_main_axis() {
+        assert_eq!(Direction::Down.vector(), (0.0, 1.0));
+        assert_eq!(Direction::Degrees(90.0).vector(), (0.0, -1.0));
+        assert_eq!(Direction::Left.vector(), (-1.0, 0.0));
+        assert_eq!(Direction::DiagonalRight.vector(), (1.0, 1.0));
+        assert_eq!(Direction::DiagonalLeftReverse.vector(), (1.0, -1.0));
+
+        let (x, y) = Direction::Degrees(300.0).vector();
+        assert!((x - 0.57735).abs() < 1e-5 && y == 1.0);
+    }
+}
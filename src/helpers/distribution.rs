@@ -0,0 +1,112 @@
+use rand::Rng;
+
+use super::utils;
+
+/// Describes how random values are distributed across a range
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Distribution {
+    /// Every value in the range is equally likely
+    #[default]
+    Uniform,
+    /// Values cluster around the middle of the range
+    Normal,
+    /// Values cluster around two peaks, a quarter of the way in from either end of the range
+    Bimodal,
+}
+
+impl Distribution {
+    /// Draws a random value between `min` and `max` from the distribution
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R, min: f32, max: f32) -> f32 {
+        if max <= min {
+            return min;
+        }
+
+        let range = max - min;
+        let value = match self {
+            Self::Uniform => utils::random_between(rng, min, max),
+            // Almost all values of a normal distribution lie within three standard deviations of the mean
+            Self::Normal => utils::random_normal(rng, min + range / 2.0, range / 6.0),
+            Self::Bimodal => {
+                let peak = if rng.gen_bool(0.5) { 0.25 } else { 0.75 };
+                utils::random_normal(rng, min + range * peak, range / 12.0)
+            }
+        };
+        value.clamp(min, max)
+    }
+}
+
+impl std::str::FromStr for Distribution {
+    type Err = ParseDistributionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "uniform" => Ok(Self::Uniform),
+            "normal" | "gaussian" => Ok(Self::Normal),
+            "bimodal" => Ok(Self::Bimodal),
+            _ => Err(ParseDistributionError::from(s.to_string())),
+        }
+    }
+}
+
+// ERROR
+// -----
+
+#[derive(Debug)]
+pub struct ParseDistributionError {
+    value: String,
+}
+
+impl From<String> for ParseDistributionError {
+    fn from(value: String) -> Self {
+        Self { value }
+    }
+}
+
+impl std::fmt::Display for ParseDistributionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unsupported distribution: {}", self.value)
+    }
+}
+
+impl std::error::Error for ParseDistributionError {}
+
+// -----
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    /// Returns the share of the samples that fall in each quarter of the range from 0 to 100
+    fn quarters(distribution: Distribution) -> [f32; 4] {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut counts = [0.0; 4];
+        for _ in 0..10_000 {
+            let value = distribution.sample(&mut rng, 0.0, 100.0);
+            assert!((0.0..=100.0).contains(&value));
+            counts[((value / 25.0) as usize).min(3)] += 1.0 / 10_000.0;
+        }
+        counts
+    }
+
+    #[test]
+    fn should_distribute_samples_across_the_range() {
+        let uniform = quarters(Distribution::Uniform);
+        assert!(uniform.iter().all(|&share| (0.2..0.3).contains(&share)));
+
+        // Most of the samples are in the middle of the range
+        let normal = quarters(Distribution::Normal);
+        assert!(normal[1] + normal[2] > 0.8);
+
+        // The samples are split between the lower and upper halves, around the two peaks
+        let bimodal = quarters(Distribution::Bimodal);
+        assert!((0.4..0.6).contains(&(bimodal[0] + bimodal[1])));
+
+        assert_eq!(
+            Distribution::Normal.sample(&mut rand::thread_rng(), 5.0, 5.0),
+            5.0
+        );
+    }
+}
@@ -1,3 +1,6 @@
+pub mod color_mode;
 pub mod colors;
 pub mod direction;
+pub mod distribution;
+mod named_colors;
 pub mod utils;
@@ -1,37 +1,39 @@
 use rand::Rng;
 
-use super::colors;
-
 //  =========
 //  UTILITIES
 //  =========
 
-/// Color string with ANSI RGBColor color code
-pub fn ansi_rgb(s: &char, color: colors::RGBColor) -> String {
-    format!(
-        "\u{001b}[38;2;{};{};{}m{}\u{001b}[0m",
-        color.r(),
-        color.g(),
-        color.b(),
-        s.to_string()
-    )
-}
-
 /// Generates a random number between `min` and `max`.
 ///
 /// #### Arguments
 ///
+/// * `rng` - The random number generator to draw from.
 /// * `min` - The lower bound of the range (inclusive).
 /// * `max` - The upper bound of the range (exclusive).
 ///
 /// #### Panics
 ///
 /// Panics if `min` is equal to or greater than `max`.
-pub fn random_between<T: PartialOrd + rand::distributions::uniform::SampleUniform>(
-    min: T,
-    max: T,
-) -> T {
-    rand::thread_rng().gen_range(min..max)
+pub fn random_between<R, T>(rng: &mut R, min: T, max: T) -> T
+where
+    R: Rng + ?Sized,
+    T: PartialOrd + rand::distributions::uniform::SampleUniform,
+{
+    rng.gen_range(min..max)
+}
+
+/// Generates a normally distributed random number with the given mean and standard deviation,
+/// using the Box-Muller transform.
+pub fn random_normal<R>(rng: &mut R, mean: f32, std_dev: f32) -> f32
+where
+    R: Rng + ?Sized,
+{
+    // `gen` yields values in [0, 1), so flip it to (0, 1] to keep clear of ln(0)
+    let u1 = 1.0 - rng.gen::<f32>();
+    let u2 = rng.gen::<f32>();
+    let z = (-2.0 * u1.ln()).sqrt() * (std::f32::consts::TAU * u2).cos();
+    mean + z * std_dev
 }
 
 #[cfg(test)]
@@ -39,16 +41,21 @@ mod tests {
     use super::*;
 
     #[test]
-    fn should_wrap_in_rgb_ansi_code() {
-        let color = colors::RGBColor(127, 102, 167);
-        let str = '#';
-        let ansi_str = "\x1b[38;2;127;102;167m#\x1b[0m";
-        assert_eq!(ansi_rgb(&str, color), ansi_str);
+    fn same_seed_returns_same_numbers() {
+        use rand::SeedableRng;
+        let mut a = rand::rngs::StdRng::seed_from_u64(7);
+        let mut b = rand::rngs::StdRng::seed_from_u64(7);
+        for _ in 0..100 {
+            assert_eq!(
+                random_between(&mut a, 0, 1000),
+                random_between(&mut b, 0, 1000)
+            );
+        }
     }
 
     #[test]
     fn no_random_number_between_0_and_1() {
-        assert_eq!(random_between(0, 1), 0);
+        assert_eq!(random_between(&mut rand::thread_rng(), 0, 1), 0);
     }
 
     #[test]
@@ -56,7 +63,7 @@ mod tests {
         let min = -15;
         let max = 45;
         for _ in 0..100 {
-            let x = random_between(min, max);
+            let x = random_between(&mut rand::thread_rng(), min, max);
             if x < min || x >= max {
                 panic!("Random number not between specified range")
             }
@@ -68,7 +75,7 @@ mod tests {
         let min = 0.5;
         let max = 5.5;
         for _ in 0..100 {
-            let x = random_between(min, max);
+            let x = random_between(&mut rand::thread_rng(), min, max);
             assert!(
                 x >= min && x < max,
                 "Random float not within specified range"
@@ -81,7 +88,7 @@ mod tests {
         let min = -50;
         let max = 50;
         for _ in 0..100 {
-            let x = random_between(min, max);
+            let x = random_between(&mut rand::thread_rng(), min, max);
             assert!(
                 x >= min && x < max,
                 "Random number not within specified range when min is negative"
@@ -96,28 +103,28 @@ mod tests {
 
     #[test]
     fn random_i32s_return_i32() {
-        let number = random_between(-15, 32);
+        let number = random_between(&mut rand::thread_rng(), -15, 32);
         let type_of_number = type_of(&number);
         assert_eq!(type_of_number, "i32");
     }
 
     #[test]
     fn random_u16s_return_u16() {
-        let number = random_between::<u16>(0, 120);
+        let number = random_between::<_, u16>(&mut rand::thread_rng(), 0, 120);
         let type_of_number = type_of(&number);
         assert_eq!(type_of_number, "u16");
     }
 
     #[test]
     fn random_u32s_return_u32() {
-        let number = random_between::<u32>(100, 1000);
+        let number = random_between::<_, u32>(&mut rand::thread_rng(), 100, 1000);
         let type_of_number = type_of(&number);
         assert_eq!(type_of_number, "u32");
     }
 
     #[test]
     fn random_i64s_return_i64() {
-        let number = random_between::<i64>(-1000, 1000);
+        let number = random_between::<_, i64>(&mut rand::thread_rng(), -1000, 1000);
         let type_of_number = type_of(&number);
         assert_eq!(type_of_number, "i64");
     }
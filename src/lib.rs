@@ -0,0 +1,36 @@
+//! The matrix-rain effect.
+//!
+//! Construct a [Matrix] from a set of [Options], [step](Matrix::step) it by a number of seconds
+//! to advance the simulation, and then either [render](Matrix::render) it onto a [Backend] or read the
+//! cells out of its [buffer](Matrix::buffer) directly.
+//!
+//! ```
+//! use matrix_rain::{Matrix, MemoryBackend, Options};
+//!
+//! let mut matrix = Matrix::new(24, 80, Options::default());
+//! let mut backend = MemoryBackend::new(24, 80);
+//! matrix.setup(&mut backend).unwrap();
+//!
+//! for _ in 0..60 {
+//!     matrix.step(1.0 / 60.0);
+//!     matrix.render(&mut backend).unwrap();
+//! }
+//!
+//! // The backend holds the same cells as the frame that was rendered onto it
+//! assert_eq!(backend.buffer(), matrix.buffer());
+//! ```
+
+pub mod export;
+pub mod helpers;
+pub mod matrix;
+pub mod renderer;
+pub mod symbols;
+
+mod options;
+
+pub use helpers::colors::{Interpolation, LinearGradient, RGBColor};
+pub use helpers::direction::Direction;
+pub use matrix::Matrix;
+pub use options::{InvalidOptionsError, Options};
+pub use renderer::{Backend, Buffer, Cell, CrosstermBackend, MemoryBackend};
+pub use symbols::Symbols;
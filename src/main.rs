@@ -1,17 +1,21 @@
 use std::io::Write;
 
-use clap::Parser;
 use crossterm::{
     cursor,
     style::{style, Stylize},
     terminal, QueueableCommand,
 };
 
+use matrix_rain::{export::CastWriter, CrosstermBackend, Matrix};
+
+mod clock;
 mod config;
 mod events;
-mod helpers;
-mod matrix;
-mod symbols;
+mod headless;
+mod themes;
+
+/// The amount the frame-rate is raised or lowered by at runtime
+const FPS_STEP: u16 = 5;
 
 //  ====
 //  MAIN
@@ -19,10 +23,9 @@ mod symbols;
 
 /// The main entrypoint of the application
 fn main() {
-    //  Parse command-line arguments as the configuration
-    let config = config::Config::parse();
-    // Run the main logic with the given command-line arguments
-    match run(&config) {
+    //  Load the configuration from the config file and command-line arguments,
+    //  and run the main logic with it
+    match config::Config::load().and_then(|config| run(&config)) {
         Err(e) => {
             eprintln!("{}", style(format!("Error: {e}")).red());
             std::process::exit(1)
@@ -33,47 +36,169 @@ fn main() {
 
 /// Run the main logic of the application
 fn run(config: &config::Config) -> std::io::Result<()> {
-    // Get a reference to stdout
-    let mut stdout = std::io::stdout();
+    // List the themes instead, if requested
+    if config.list_themes {
+        themes::list(config.color_mode);
+        return Ok(());
+    }
+
+    // Run without a terminal or export the animation instead, if requested
+    if config.headless {
+        return headless::dump(config);
+    }
+    if let Some(path) = &config.export_gif {
+        return headless::export_gif(config, path);
+    }
+    if let Some(path) = &config.export_svg {
+        return headless::export_svg(config, path);
+    }
 
     //  Get Terminal Window Size to determine the number of rows and columns
     let (columns, rows) = terminal::size()?;
 
+    // The output everything is written to. Recorded as an asciicast, if requested
+    let output = match &config.record {
+        Some(path) => {
+            let file = std::fs::File::create(path)
+                .map_err(|e| std::io::Error::new(e.kind(), format!("{}: {e}", path.display())))?;
+            let cast = std::io::BufWriter::new(file);
+            Output::Recorded(CastWriter::new(std::io::stdout(), cast, columns, rows)?)
+        }
+        None => Output::Terminal(std::io::stdout()),
+    };
+
+    // The backend the matrix is drawn onto
+    let mut backend = CrosstermBackend::new(output).with_color_mode(config.color_mode);
+
     //  Instantiate the matrix streams
-    let mut matrix = matrix::Matrix::new(rows, columns, &config);
+    let mut matrix = Matrix::new(rows, columns, config.into());
 
     // Setup the terminal before running the application
-    setup(&mut stdout)?;
+    setup(backend.writer_mut())?;
 
     // Setup the Matrix renderer
-    matrix.setup(config, &mut stdout)?;
+    matrix.setup(&mut backend)?;
+
+    // The direction the streams started out in, which cycling through the directions returns to
+    let initial_direction = matrix.options().direction.clone();
+
+    // Whether the animation is frozen on the current frame
+    let mut paused = false;
+
+    // The clock that times the frames, independent of input
+    let mut clock = clock::FrameClock::new(matrix.options().fps);
 
     //  Render the Matrix-Rain on screen
     loop {
-        //  Render each stream
-        matrix.render(&config, &mut stdout)?;
-
-        // Handle events
-        if crossterm::event::poll(std::time::Duration::from_millis(1000 / config.fps as u64))? {
-            if let events::Action::Exit = events::handle_events()? {
-                break;
+        // Handle events while waiting for the next frame to be due
+        if crossterm::event::poll(clock.time_until_next_frame())? {
+            let fps = matrix.options().fps;
+            match events::handle_events(paused)? {
+                events::Action::Exit => break,
+                events::Action::Resize(columns, rows) => {
+                    if let Output::Recorded(cast) = backend.writer_mut() {
+                        cast.resize(columns, rows)?;
+                    }
+                    matrix.resize(rows, columns, &mut backend)?;
+                    // Redraw the frame straight away, as it won't be re-rendered while paused
+                    matrix.render(&mut backend)?;
+                }
+                events::Action::Pause => paused = true,
+                events::Action::Resume => paused = false,
+                events::Action::Step => {
+                    matrix.step(clock.timestep());
+                    matrix.render(&mut backend)?;
+                }
+                events::Action::IncreaseFps => {
+                    matrix.set_fps(fps.saturating_add(FPS_STEP));
+                    clock.set_fps(matrix.options().fps);
+                }
+                events::Action::DecreaseFps => {
+                    matrix.set_fps(fps.saturating_sub(FPS_STEP));
+                    clock.set_fps(matrix.options().fps);
+                }
+                events::Action::IncreaseSpacing => {
+                    matrix.set_stream_spacing(matrix.options().stream_spacing.saturating_add(1))
+                }
+                events::Action::DecreaseSpacing => {
+                    matrix.set_stream_spacing(matrix.options().stream_spacing.saturating_sub(1))
+                }
+                events::Action::NextMode => matrix.set_mode(matrix.options().mode.next()),
+                events::Action::NextDirection => {
+                    let direction = matrix.options().direction.next_from(&initial_direction);
+                    matrix.set_direction(direction)
+                }
+                events::Action::ToggleTrail => {
+                    matrix.set_leave_trail(!matrix.options().leave_trail)
+                }
+                events::Action::None => (),
             }
         }
+
+        // Keep waiting until the next frame is due
+        if !clock.time_until_next_frame().is_zero() {
+            continue;
+        }
+
+        //  Step the simulation for each timestep that has passed, unless paused
+        let steps = clock.tick();
+        if paused || steps == 0 {
+            continue;
+        }
+        for _ in 0..steps {
+            matrix.step(clock.timestep());
+        }
+
+        // Overlay the measured frame-rate, if requested
+        if config.show_fps {
+            let text = format!(" {:.1} fps ", clock.measured_fps());
+            matrix
+                .buffer_mut()
+                .print(0, 0, &text, config.leading_entity_color);
+        }
+
+        //  Render the frame
+        matrix.render(&mut backend)?;
+        clock.frame_rendered();
     }
 
     // Cleanup the terminal after the application stops
-    cleanup(&mut stdout)?;
+    cleanup(backend.writer_mut())?;
 
     Ok(())
 }
 
+/// The output everything is written to
+enum Output {
+    /// Written straight to the terminal
+    Terminal(std::io::Stdout),
+    /// Written to the terminal, and recorded as an asciicast
+    Recorded(CastWriter<std::io::Stdout, std::io::BufWriter<std::fs::File>>),
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Terminal(stdout) => stdout.write(buf),
+            Self::Recorded(cast) => cast.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::Terminal(stdout) => stdout.flush(),
+            Self::Recorded(cast) => cast.flush(),
+        }
+    }
+}
+
 // HELPER FUNCTIONS
 // ----------------
 
 /// Prepares the terminal by switching to the alternate screen and clearing it.
 /// Also moves the cursor to the top before hiding it from view.
 /// Registers a panic-hook to automatically call the `cleanup` function
-fn setup(stdout: &mut std::io::Stdout) -> std::io::Result<()> {
+fn setup(stdout: &mut impl Write) -> std::io::Result<()> {
     terminal::enable_raw_mode()?;
     stdout
         .queue(terminal::EnterAlternateScreen)?
@@ -96,7 +221,7 @@ fn setup(stdout: &mut std::io::Stdout) -> std::io::Result<()> {
 
 /// Restores terminal to its original state by leaving alternate screen,
 /// showing the cursor, and disabling raw mode.
-fn cleanup(stdout: &mut std::io::Stdout) -> std::io::Result<()> {
+fn cleanup(stdout: &mut impl Write) -> std::io::Result<()> {
     stdout
         .queue(terminal::LeaveAlternateScreen)?
         .queue(cursor::Show)?
@@ -1,9 +1,8 @@
-use crossterm::cursor;
-use crossterm::style::Print;
-use crossterm::QueueableCommand;
+use rand::Rng;
 
-use crate::config;
 use crate::helpers::{colors, utils};
+use crate::renderer::{Buffer, Cell};
+use crate::Options;
 
 use super::symbols;
 
@@ -18,9 +17,9 @@ pub struct Entity {
     pub x: f32,
     /// The y-position
     pub y: f32,
-    /// The speed along the x-axis
+    /// The speed along the x-axis (in cells per second)
     speed_x: f32,
-    /// The speed along the y-axis
+    /// The speed along the y-axis (in cells per second)
     speed_y: f32,
 
     /// The symbol the entity represents
@@ -29,14 +28,15 @@ pub struct Entity {
     color: colors::RGBColor,
     /// The character set to use for the symbols
     mode: symbols::Symbols,
-    /// The frame-count since last symbol switch.
+    /// The number of seconds since the last symbol switch.
     /// When this number reaches the `switch_interval`, the character symbol
     /// is swapped for another one, chosen randomly, from the symbol character set.
-    frame_count: u16,
-    /// The number of frames before a symbol switch.
-    /// When the `frame_count` reaches this value, the symbol is swapped
+    elapsed: f32,
+    /// The number of seconds before a symbol switch.
+    /// When `elapsed` reaches this value, the symbol is swapped
     /// for another one, chosen randomly, from the symbol character set.
-    switch_interval: u16,
+    /// A value of zero means the symbol never switches.
+    switch_interval: f32,
 }
 
 impl Entity {
@@ -47,7 +47,8 @@ impl Entity {
         speed_x: f32,
         speed_y: f32,
         color: colors::RGBColor,
-        config: &config::Config,
+        options: &Options,
+        rng: &mut impl Rng,
     ) -> Self {
         Self {
             x,
@@ -56,54 +57,82 @@ impl Entity {
             speed_y,
             color,
             symbol: ' ',
-            mode: config.mode.clone(),
-            frame_count: 0,
-            switch_interval: utils::random_between::<u16>(1, config.switch_interval * config.fps),
+            mode: options.mode.clone(),
+            elapsed: 0.0,
+            switch_interval: match options.switch_interval {
+                0 => 0.0,
+                max => utils::random_between(rng, 0.0, max as f32),
+            },
         }
     }
 
-    /// Rain. Updates the position of the [Entity] using the rain speed.
-    pub fn rain(&mut self) {
-        self.x += self.speed_x;
-        self.y += self.speed_y;
+    /// Returns the velocity of the [Entity] as (speed_x, speed_y), in cells per second
+    pub fn velocity(&self) -> (f32, f32) {
+        (self.speed_x, self.speed_y)
+    }
+
+    /// Accelerates the [Entity] by the given acceleration (in cells per second squared),
+    /// for the given number of seconds. The speed is capped at the `max_speed`, unless it's zero.
+    pub fn accelerate(&mut self, acceleration: (f32, f32), max_speed: f32, dt: f32) {
+        self.speed_x += acceleration.0 * dt;
+        self.speed_y += acceleration.1 * dt;
+
+        let speed = self.speed_x.hypot(self.speed_y);
+        if max_speed > 0.0 && speed > max_speed {
+            self.speed_x *= max_speed / speed;
+            self.speed_y *= max_speed / speed;
+        }
+    }
+
+    /// Rain. Updates the position of the [Entity] using the rain speed and the wind
+    /// (in cells per second), for the given number of seconds that have passed.
+    pub fn rain(&mut self, dt: f32, wind: (f32, f32)) {
+        self.x += (self.speed_x + wind.0) * dt;
+        self.y += (self.speed_y + wind.1) * dt;
+        self.elapsed += dt;
+    }
+
+    /// Changes the character set the [Entity] picks its symbols from
+    pub fn set_mode(&mut self, mode: &symbols::Symbols) {
+        self.mode = mode.clone();
     }
 
     /// Updates the [Entity] symbol by picking one randomly from the symbol set
-    pub fn set_symbol(&mut self) {
-        self.symbol = self.mode.get_random();
+    pub fn set_symbol(&mut self, rng: &mut impl Rng) {
+        self.symbol = self.mode.get_random(rng);
     }
 
-    /// If the `frame_count` has exceeded `switch_interval` switch the [Entity] symbol to
+    /// If the `elapsed` time has exceeded `switch_interval` switch the [Entity] symbol to
     /// another one from the character set.
-    fn switch_symbol(&mut self) {
-        if self.frame_count % self.switch_interval == 0 {
-            self.set_symbol();
+    fn switch_symbol(&mut self, rng: &mut impl Rng) {
+        if self.elapsed >= self.switch_interval {
+            self.set_symbol(rng);
+            self.elapsed = 0.0;
         }
-        self.frame_count += 1;
     }
 
-    /// Render Entity on screen
-    pub fn render(
-        &mut self,
-        rows: i32,
-        columns: i32,
-        stdout: &mut std::io::Stdout,
-    ) -> std::io::Result<()> {
+    /// Render Entity into the frame [Buffer], with the hue of its color rotated by the given number of degrees
+    pub fn render(&mut self, buffer: &mut Buffer, hue: f32, rng: &mut impl Rng) {
         // Don't render if the entity is off-screen
-        if self.x < 0.0 || self.x >= columns as f32 || self.y < 0.0 || self.y >= rows as f32 {
-            return Ok(());
+        if self.x < 0.0
+            || self.x >= buffer.columns() as f32
+            || self.y < 0.0
+            || self.y >= buffer.rows() as f32
+        {
+            return;
         }
 
-        // Move cursor to position and write symbol
-        stdout
-            .queue(cursor::MoveTo(self.x as u16, self.y as u16))?
-            .queue(Print(utils::ansi_rgb(&self.symbol, self.color)))?;
+        // Write the symbol into the cell at the entity's position
+        let color = if hue == 0.0 {
+            self.color
+        } else {
+            self.color.rotate_hue(hue)
+        };
+        buffer.set(self.x as u16, self.y as u16, Cell::new(self.symbol, color));
 
-        // Switch symbol if `frame_count` exceeds `switch_interval`
-        if self.switch_interval != 0 {
-            self.switch_symbol();
+        // Switch symbol if `elapsed` exceeds `switch_interval`
+        if self.switch_interval > 0.0 {
+            self.switch_symbol(rng);
         }
-
-        Ok(())
     }
 }
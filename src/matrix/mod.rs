@@ -1,19 +1,14 @@
-use std::io::Write;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
 use super::{
-    config,
     helpers::{direction::Direction, utils},
-    symbols,
+    renderer::{Backend, Buffer, Cell},
+    symbols, InvalidOptionsError, Options,
 };
 
 mod entity;
 mod stream;
 
-use crossterm::{
-    cursor::{self, MoveToNextLine},
-    style::Print,
-    QueueableCommand,
-};
 use stream::Stream;
 
 //  ======
@@ -27,139 +22,687 @@ pub struct Matrix {
     /// Number of columns
     columns: u16,
 
+    /// The options that describe the look and behavior of the Matrix
+    options: Options,
+
+    /// The random number generator that drives the Matrix.
+    /// Seeded from the options, if a seed was provided, for reproducible output.
+    rng: StdRng,
+
     /// Collection of matrix [entity](entity::Entity) streams
     streams: Vec<Stream>,
+
+    /// The frame [Buffer] the streams are rendered into
+    buffer: Buffer,
+    /// The frame [Buffer] that was last drawn on screen
+    previous: Buffer,
+
+    /// The number of degrees the hues of all streams are rotated by, as they cycle over time
+    hue: f32,
+    /// The number of seconds the Matrix has been stepped forward by, which the wind gusts over
+    elapsed: f32,
 }
 
 impl Matrix {
     /// Construct a new Matrix instance
-    pub fn new(rows: u16, columns: u16, config: &config::Config) -> Self {
+    ///
+    /// #### Panics
+    ///
+    /// Panics if the options aren't [valid](Options::validate). Use [Matrix::try_new] to handle invalid options.
+    pub fn new(rows: u16, columns: u16, options: Options) -> Self {
+        Self::try_new(rows, columns, options).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Construct a new Matrix instance, if the options are [valid](Options::validate)
+    pub fn try_new(rows: u16, columns: u16, options: Options) -> Result<Self, InvalidOptionsError> {
+        options.validate()?;
+
         // Instantiate Self
         let mut ret = Self {
             rows,
             columns,
+            rng: match options.seed {
+                Some(seed) => StdRng::seed_from_u64(seed),
+                None => StdRng::from_entropy(),
+            },
+            options,
             streams: Vec::new(),
-        };
-
-        //  Determine the count of streams to generate
-        let count = match config.direction {
-            Direction::Up | Direction::Down => ret.columns,
-            Direction::Right | Direction::Left => ret.rows,
-            Direction::DiagonalRight
-            | Direction::DiagonalRightReverse
-            | Direction::DiagonalLeft
-            | Direction::DiagonalLeftReverse => ret.columns + ret.rows, // We need more columns to cover the entire view-space when moving diagonally
+            buffer: Buffer::new(rows, columns),
+            previous: Buffer::new(rows, columns),
+            hue: 0.0,
+            elapsed: 0.0,
         };
 
         // Generate the Matrix Streams
-        for c in 0..count {
-            // Space out the streams, if specified in the configuration
-            if c % config.stream_spacing != 0 {
-                continue;
-            }
+        ret.add_streams();
 
-            // Determine the starting x and y positions based on the direction of flow
-            let (x, y) = match config.direction {
-                Direction::Down => {
-                    let offset = utils::random_between(-50, 0);
-                    (c as f32, offset as f32)
-                }
-                Direction::Up => {
-                    let offset = utils::random_between(ret.rows, ret.rows + 50);
-                    (c as f32, offset as f32)
-                }
-                Direction::Right => {
-                    let offset = utils::random_between(-50, 0);
-                    (offset as f32, c as f32)
-                }
-                Direction::Left => {
-                    let offset = utils::random_between(ret.columns, ret.columns + 50);
-                    (offset as f32, c as f32)
-                }
-                Direction::DiagonalLeft => {
-                    let x_offset = c as f32 + (ret.columns + ret.rows) as f32 / 2.0;
-                    let y_offset = utils::random_between(-50, 0);
-                    (x_offset, y_offset as f32)
-                }
-                Direction::DiagonalLeftReverse => {
-                    let x_offset = c as f32 - (ret.columns + ret.rows) as f32 / 2.0;
-                    let y_offset = utils::random_between(ret.rows, ret.rows + 50);
-                    (x_offset, y_offset as f32)
-                }
-                Direction::DiagonalRight => {
-                    let x_offset = c as f32 - (ret.columns + ret.rows) as f32 / 2.0;
-                    let y_offset = utils::random_between(-50, 0);
-                    (x_offset, y_offset as f32)
-                }
-                Direction::DiagonalRightReverse => {
-                    let x_offset = c as f32 + (ret.columns + ret.rows) as f32 / 2.0;
-                    let y_offset = utils::random_between(ret.rows, ret.rows + 50);
-                    (x_offset, y_offset as f32)
-                }
-            };
+        // Fill in the background
+        ret.fill_background();
 
-            // Instantiate a Stream
-            let stream = Stream::new(x, y, config);
+        // Return the instance
+        Ok(ret)
+    }
+
+    /// Determine the count of lanes the streams can occupy, based on the direction of flow
+    fn lanes(&self) -> u16 {
+        lanes(&self.options.direction, self.rows, self.columns)
+    }
+
+    /// Determine the spacing between the lanes of the streams.
+    /// Streams that flow across the columns are spaced out by at least the width of the symbols,
+    /// so that double-width symbols don't overlap the neighbouring streams.
+    fn spacing(&self) -> u16 {
+        if flows_vertically(&self.options.direction) {
+            self.options.stream_spacing.max(self.options.mode.width())
+        } else {
+            self.options.stream_spacing
+        }
+    }
+
+    /// Generate streams for all the lanes that don't have one yet
+    fn add_streams(&mut self) {
+        // Space out the streams, if specified in the configuration
+        let lanes = (0..self.lanes())
+            .step_by(self.spacing() as usize)
+            .skip(self.streams.len());
+
+        for c in lanes {
+            // Instantiate a Stream, flowing in a direction of its own if they're mixed
+            let direction = self.options.direction.choose(&mut self.rng);
+            let (x, y) = self.spawn_position(&direction, c);
+            let mut stream = Stream::new(x, y, c, direction, &self.options, &mut self.rng);
+            stream.set_hue(self.lane_hue(c));
 
             //  Add stream to vector collection
-            ret.streams.push(stream);
+            self.streams.push(stream);
         }
+    }
 
-        // Return the instance
-        return ret;
+    /// Determine the hue the stream in the given lane is rotated by.
+    /// In rainbow mode, the lanes are spread across the entire hue circle.
+    fn lane_hue(&self, c: u16) -> f32 {
+        if self.options.rainbow {
+            c as f32 / self.lanes().max(1) as f32 * 360.0
+        } else {
+            0.0
+        }
     }
 
-    /// The setup function is called once before the draw loop starts
-    pub fn setup(
+    /// Determine the starting x and y positions of a stream flowing in the given direction, in the given lane
+    fn spawn_position(&mut self, direction: &Direction, c: u16) -> (f32, f32) {
+        let offset = spawn_offset(self.options.spawn_window, &mut self.rng);
+        let layout = &self.options.direction;
+        spawn_position(layout, direction, c, self.rows, self.columns, offset)
+    }
+
+    /// Resize the Matrix to the given number of rows and columns.
+    ///
+    /// Streams whose lanes no longer fit are removed, and new streams are generated for any new lanes.
+    /// The remaining streams carry on where they are, and respawn relative to the new size.
+    pub fn resize<B: Backend>(
         &mut self,
-        config: &config::Config,
-        stdout: &mut std::io::Stdout,
+        rows: u16,
+        columns: u16,
+        backend: &mut B,
     ) -> std::io::Result<()> {
+        let (old_rows, old_columns) = (self.rows, self.columns);
+        self.rows = rows;
+        self.columns = columns;
+
+        // Remove the streams whose lanes no longer fit
+        let spacing = self.spacing();
+        self.streams
+            .truncate(self.lanes().div_ceil(spacing) as usize);
+
+        // Move the spawn points of the remaining streams to match the new size
+        for i in 0..self.streams.len() {
+            let c = i as u16 * spacing;
+            let direction = self.streams[i].direction().clone();
+            let (x, y) = self.spawn_position(&direction, c);
+            let hue = self.lane_hue(c);
+            self.streams[i].set_origin(x, y);
+            self.streams[i].set_hue(hue);
+        }
+
+        // Generate streams for the new lanes
+        self.add_streams();
+
+        // Resize the frame buffers. The screen is cleared, so the next frame is drawn in its entirety
+        self.buffer.resize(rows, columns);
+        self.previous = Buffer::new(rows, columns);
+        self.fill_background();
+        backend.clear()?;
+
+        // Keep the trail covering the whole screen, including the area uncovered by the resize
+        if self.options.leave_trail {
+            self.populate_background_outside(old_rows, old_columns);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the options that describe the look and behavior of the Matrix
+    pub fn options(&self) -> &Options {
+        &self.options
+    }
+
+    /// Changes the frame-rate the Matrix is stepped at
+    pub fn set_fps(&mut self, fps: u16) {
+        self.options.fps = fps.max(1);
+    }
+
+    /// Changes the spacing between the streams, regenerating them to fit.
+    /// The spacing is kept between 1 and the number of lanes, so that there is always a stream.
+    pub fn set_stream_spacing(&mut self, spacing: u16) {
+        self.options.stream_spacing = spacing.clamp(1, self.lanes().max(1));
+        self.streams.clear();
+        self.add_streams();
+    }
+
+    /// Changes the character symbol set of all streams.
+    /// The streams are regenerated if the width of the symbols changes, to space them out accordingly.
+    pub fn set_mode(&mut self, mode: symbols::Symbols) {
+        let width = self.options.mode.width();
+        for stream in self.streams.iter_mut() {
+            stream.set_mode(&mode);
+        }
+        self.options.mode = mode;
+
+        if self.options.mode.width() != width {
+            self.streams.clear();
+            self.add_streams();
+        }
+    }
+
+    /// Changes the direction of motion, regenerating the streams to flow the new way
+    pub fn set_direction(&mut self, direction: Direction) {
+        self.options.direction = direction;
+        self.streams.clear();
+        self.add_streams();
+    }
+
+    /// Changes whether the streams leave their trail intact
+    pub fn set_leave_trail(&mut self, leave_trail: bool) {
+        self.options.leave_trail = leave_trail;
+    }
+
+    /// Returns the frame [Buffer] holding the cells of the current frame
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Returns the frame [Buffer] mutably, to draw on top of the current frame before it's rendered
+    pub fn buffer_mut(&mut self) -> &mut Buffer {
+        &mut self.buffer
+    }
+
+    /// The setup function is called once before the draw loop starts
+    pub fn setup<B: Backend>(&mut self, backend: &mut B) -> std::io::Result<()> {
+        // Start from a blank surface, as that's what the first frame is diffed against
+        backend.clear()?;
+
         // Pre-populate the matrix background with random symbols if the `--leave_trail` option was set
-        if config.leave_trail {
-            self.populate_background(config, stdout)?;
+        if self.options.leave_trail {
+            self.populate_background();
         }
         Ok(())
     }
 
-    /// Render the Matrix
-    pub fn render(
-        &mut self,
-        config: &config::Config,
-        stdout: &mut std::io::Stdout,
-    ) -> std::io::Result<()> {
+    /// Step the Matrix forward by the given number of seconds, rendering the streams into the frame [Buffer]
+    pub fn step(&mut self, dt: f32) {
+        // Unless we're leaving a trail, every frame is drawn from scratch
+        if !self.options.leave_trail {
+            self.buffer.clear();
+        }
+
+        // Rotate the hues, if they cycle over time
+        self.hue = (self.hue + self.options.hue_cycle * dt).rem_euclid(360.0);
+        self.elapsed += dt;
+        let wind = self.wind();
+
+        // Render each stream into the frame buffer
         for stream in self.streams.iter_mut() {
-            stream.render(self.rows as i32, self.columns as i32, config, stdout)?;
+            stream.render(
+                &self.options,
+                &mut self.buffer,
+                dt,
+                self.hue,
+                wind,
+                &mut self.rng,
+            );
         }
-        stdout.flush()?;
+    }
+
+    /// Determine the current wind, in cells per second. The strength of the wind rises and falls
+    /// smoothly over time, by up to the share given by the `wind_gust` option.
+    fn wind(&self) -> (f32, f32) {
+        let (x, y) = self.options.wind;
+        let t = self.elapsed;
+        // Two out-of-step waves make for gusts that don't repeat too obviously
+        let gust = 0.6 * (t * 0.7).sin() + 0.4 * (t * 1.9 + 1.3).sin();
+        let strength = (1.0 + self.options.wind_gust * gust).max(0.0);
+        (x * strength, y * strength)
+    }
+
+    /// Render the current frame onto the [Backend]
+    pub fn render<B: Backend>(&mut self, backend: &mut B) -> std::io::Result<()> {
+        // Only draw the cells that changed since the last frame
+        backend.draw(self.buffer.diff(&self.previous))?;
+        backend.flush()?;
+        self.previous.clone_from(&self.buffer);
+
         Ok(())
     }
 
+    /// Fill the rows of the frame [Buffer] with the background color, or the background gradient
+    fn fill_background(&mut self) {
+        let gradient = self
+            .options
+            .background_gradient
+            .clone()
+            .map(|gradient| gradient.with_interpolation(self.options.gradient_interpolation));
+
+        for y in 0..self.rows {
+            let color = match &gradient {
+                Some(gradient) => {
+                    Some(gradient.interpolate(y as f32 / self.rows.saturating_sub(1).max(1) as f32))
+                }
+                None => self.options.background_color,
+            };
+            self.buffer.set_row_background(y, color);
+        }
+    }
+
     /// Pre-populate the background with faint symbols before the first render
-    pub fn populate_background(
-        &mut self,
-        config: &config::Config,
-        stdout: &mut std::io::Stdout,
-    ) -> std::io::Result<()> {
+    pub fn populate_background(&mut self) {
+        self.populate_background_outside(0, 0);
+    }
+
+    /// Populate the background with faint symbols, outside of the given number of rows and columns
+    /// that are already populated, like the area uncovered by growing the Matrix
+    fn populate_background_outside(&mut self, rows: u16, columns: u16) {
         // Determine the faint color of the trailing end of streams
-        let clr = config.stream_color * config.stream_color_gradient_factor;
-        // Go to the home position and set the RGB ANSI code
-        stdout.queue(cursor::MoveTo(0, 0))?.queue(Print(format!(
-            "\x1b[38;2;{};{};{}m",
-            clr.r(),
-            clr.g(),
-            clr.b()
-        )))?;
-        // Iterate over each row and pre-render random faint symbols
-        for _ in 0..self.rows {
-            let random_symbol_str =
-                String::from_iter((0..self.columns).map(|_| config.mode.get_random()));
-            stdout
-                .queue(Print(random_symbol_str))?
-                .queue(MoveToNextLine(1))?;
-        }
-        // Reset the ANSI color codes
-        stdout.queue(Print("\x1b[0m"))?.flush()?;
-        Ok(())
+        let clr = self.options.gradient().interpolate(1.0);
+        // Fill each cell with a random faint symbol. Double-width symbols fill two cells at once
+        let width = self.options.mode.width() as usize;
+        for y in 0..self.rows {
+            let start = if y < rows { columns } else { 0 };
+            for x in (start..self.columns).step_by(width) {
+                self.buffer.set(
+                    x,
+                    y,
+                    Cell::new(self.options.mode.get_random(&mut self.rng), clr),
+                );
+            }
+        }
+    }
+}
+
+//  ========
+//  GEOMETRY
+//  ========
+
+/// Determine whether streams flowing in the given direction flow mostly up or down the screen (rather than across it).
+/// Such streams spawn along the top or bottom edge, and their lanes run across the columns.
+fn flows_vertically(direction: &Direction) -> bool {
+    let (dx, dy) = direction.vector();
+    dy.abs() >= dx.abs()
+}
+
+/// Determine a random distance off-screen for a stream to start at, within the spawn window
+fn spawn_offset(spawn_window: u16, rng: &mut impl Rng) -> f32 {
+    match spawn_window {
+        0 => 0.0,
+        window => utils::random_between(rng, 0, window) as f32,
+    }
+}
+
+/// Determine the count of lanes the streams flowing in the given direction can occupy.
+/// Slanted streams drift sideways as they cross the screen, so more lanes are needed to cover the entire view-space.
+fn lanes(direction: &Direction, rows: u16, columns: u16) -> u16 {
+    let (dx, dy) = direction.vector();
+    let (rows, columns) = (rows as f32, columns as f32);
+    let lanes = if flows_vertically(direction) {
+        columns + dx.abs() * rows
+    } else {
+        rows + dy.abs() * columns
+    };
+    lanes.ceil() as u16
+}
+
+/// Determine the starting x and y positions of a stream flowing in the given direction, in lane `c` of the `layout`.
+/// The streams start `offset` cells off the edge of the screen behind them, with the lanes shifted back by
+/// the distance the streams drift sideways while crossing the screen. If the stream flows in a different direction
+/// than the one the lanes are laid out across (when the directions are mixed), the lane is scaled to fit.
+fn spawn_position(
+    layout: &Direction,
+    direction: &Direction,
+    c: u16,
+    rows: u16,
+    columns: u16,
+    offset: f32,
+) -> (f32, f32) {
+    let scale = lanes(direction, rows, columns) as f32 / lanes(layout, rows, columns).max(1) as f32;
+    let c = (c as f32 * scale).floor();
+    let (dx, dy) = direction.vector();
+    let (rows, columns) = (rows as f32, columns as f32);
+    if flows_vertically(direction) {
+        let y = if dy > 0.0 {
+            -1.0 - offset
+        } else {
+            rows + offset
+        };
+        (c - dx.max(0.0) * rows, y)
+    } else {
+        let x = if dx > 0.0 {
+            -1.0 - offset
+        } else {
+            columns + offset
+        };
+        (x, c - dy.max(0.0) * columns)
+    }
+}
+
+// -----
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::{CrosstermBackend, MemoryBackend};
+
+    /// Renders a number of frames with the given options and returns the emitted bytes
+    fn render_frames(options: Options, frames: usize) -> Vec<u8> {
+        let mut matrix = Matrix::new(24, 80, options);
+        let mut backend = CrosstermBackend::new(Vec::new());
+        matrix.setup(&mut backend).unwrap();
+        for _ in 0..frames {
+            matrix.step(1.0 / 60.0);
+            matrix.render(&mut backend).unwrap();
+        }
+        backend.writer().clone()
+    }
+
+    #[test]
+    fn same_seed_renders_identical_output() {
+        let options = Options::default().seed(42);
+        assert_eq!(
+            render_frames(options.clone(), 120),
+            render_frames(options, 120)
+        );
+    }
+
+    #[test]
+    fn invalid_options_are_refused() {
+        let options = Options::default().stream_count(5, 5);
+        assert!(Matrix::try_new(10, 10, options).is_err());
+        assert!(Matrix::try_new(10, 10, Options::default()).is_ok());
+    }
+
+    #[test]
+    fn resize_adds_and_removes_streams() {
+        let mut matrix = Matrix::new(24, 80, Options::default().seed(7));
+        let mut backend = MemoryBackend::new(24, 80);
+        assert_eq!(matrix.streams.len(), 40);
+
+        matrix.step(1.0 / 60.0);
+        matrix.resize(24, 41, &mut backend).unwrap();
+        assert_eq!(matrix.streams.len(), 21);
+        assert_eq!(matrix.buffer().columns(), 41);
+
+        matrix.resize(30, 120, &mut backend).unwrap();
+        assert_eq!(matrix.streams.len(), 60);
+        matrix.step(1.0 / 60.0);
+        matrix.render(&mut backend).unwrap();
+    }
+
+    #[test]
+    fn resize_extends_the_trail() {
+        let options = Options::default().seed(7).mode(symbols::Symbols::Binary);
+        let mut matrix = Matrix::new(24, 80, options.leave_trail(true));
+        let mut backend = MemoryBackend::new(30, 100);
+        matrix.setup(&mut backend).unwrap();
+        matrix.resize(30, 100, &mut backend).unwrap();
+        matrix.render(&mut backend).unwrap();
+        for y in 0..30 {
+            for x in 0..100 {
+                assert_ne!(backend.buffer().get(x, y).map(|c| c.symbol), Some(' '));
+            }
+        }
+    }
+
+    #[test]
+    fn changing_spacing_and_direction_regenerates_streams() {
+        let mut matrix = Matrix::new(24, 80, Options::default().seed(7));
+        matrix.set_stream_spacing(4);
+        assert_eq!(matrix.streams.len(), 20);
+        matrix.set_direction(Direction::Left);
+        assert_eq!(matrix.streams.len(), 6);
+        matrix.set_stream_spacing(0);
+        assert_eq!(matrix.options().stream_spacing, 1);
+        matrix.set_stream_spacing(u16::MAX);
+        assert_eq!(matrix.options().stream_spacing, 24);
+        assert_eq!(matrix.streams.len(), 1);
+        matrix.step(1.0 / 60.0);
+    }
+
+    #[test]
+    fn double_width_symbols_space_out_the_streams() {
+        let options = Options::default().seed(7).stream_spacing(1);
+        let mut matrix = Matrix::new(24, 80, options.mode(symbols::Symbols::Binary));
+        assert_eq!(matrix.streams.len(), 80);
+        matrix.set_mode(symbols::Symbols::Original);
+        assert_eq!(matrix.streams.len(), 40);
+        matrix.set_direction(Direction::Right);
+        assert_eq!(matrix.streams.len(), 24);
+    }
+
+    #[test]
+    fn rainbow_gives_each_lane_its_own_hue() {
+        let mut matrix = Matrix::new(24, 80, Options::default().seed(7).rainbow(true));
+        let hues: Vec<f32> = (0..4).map(|c| matrix.lane_hue(c * 20)).collect();
+        assert_eq!(hues, [0.0, 90.0, 180.0, 270.0]);
+
+        // The hues cycle over time, and the colors of the streams follow
+        let mut matrix_cycling = Matrix::new(24, 80, Options::default().seed(7).hue_cycle(90.0));
+        for _ in 0..120 {
+            matrix.step(1.0 / 60.0);
+            matrix_cycling.step(1.0 / 60.0);
+        }
+        assert!((matrix_cycling.hue - 180.0).abs() < 0.01);
+        assert_ne!(matrix.buffer(), matrix_cycling.buffer());
+    }
+
+    #[test]
+    fn background_fills_every_cell() {
+        let black = crate::RGBColor(0, 20, 0);
+        let mut matrix = Matrix::new(24, 80, Options::default().seed(7).background_color(black));
+        let mut backend = MemoryBackend::new(24, 80);
+        matrix.setup(&mut backend).unwrap();
+        matrix.step(1.0 / 60.0);
+        matrix.render(&mut backend).unwrap();
+        for y in 0..24 {
+            for x in 0..80 {
+                assert_eq!(backend.buffer().get(x, y).and_then(|c| c.bg), Some(black));
+            }
+        }
+
+        let gradient = "#000,#00f".parse().unwrap();
+        matrix = Matrix::new(3, 4, Options::default().background_gradient(gradient));
+        matrix.step(1.0 / 60.0);
+        let bg = |y| matrix.buffer().get(0, y).and_then(|c| c.bg);
+        assert_eq!(
+            (bg(0), bg(1), bg(2)),
+            (
+                Some(crate::RGBColor(0, 0, 0)),
+                Some(crate::RGBColor(0, 0, 128)),
+                Some(crate::RGBColor(0, 0, 255))
+            )
+        );
+    }
+
+    #[test]
+    fn streams_spawn_within_the_spawn_window() {
+        let options = Options::default().seed(7).spawn_window(10);
+        let matrix = Matrix::new(24, 80, options.clone());
+        let leaders = |matrix: &Matrix| -> Vec<(f32, f32)> {
+            matrix
+                .streams
+                .iter()
+                .map(|s| (s.entities[0].x, s.entities[0].y))
+                .collect()
+        };
+        assert!(leaders(&matrix)
+            .iter()
+            .all(|(_, y)| (-10.0..0.0).contains(y)));
+
+        let mut matrix = Matrix::new(24, 80, options.spawn_window(0));
+        assert!(leaders(&matrix).iter().all(|(_, y)| *y == -1.0));
+        matrix.set_direction(Direction::Left);
+        assert!(leaders(&matrix).iter().all(|(x, _)| *x == 80.0));
+    }
+
+    #[test]
+    fn streams_flow_at_any_angle() {
+        // The named directions are just angles
+        let options = Options::default().seed(7);
+        assert_eq!(
+            render_frames(options.clone().direction(Direction::Degrees(270.0)), 60),
+            render_frames(options.clone(), 60)
+        );
+
+        // Slanted streams get extra lanes, to cover the screen as they drift sideways
+        let mut matrix = Matrix::new(24, 80, options.spawn_window(0));
+        matrix.set_direction(Direction::Degrees(300.0));
+        assert_eq!(matrix.lanes(), 80 + 14);
+        matrix.set_direction(Direction::Degrees(10.0));
+        assert_eq!(matrix.lanes(), 24 + 15);
+        assert!(matrix.streams.iter().all(|s| s.entities[0].x == -1.0));
+
+        // The streams cross the screen along the angle, and keep coming back
+        for _ in 0..600 {
+            matrix.step(1.0 / 60.0);
+        }
+        let (x, y) = matrix.streams[0].entities[0].velocity();
+        assert!((y / x + 0.17633).abs() < 1e-4);
+        let buffer = matrix.buffer();
+        assert!((0..24).any(|y| (0..80).any(|x| buffer.get(x, y).unwrap().symbol != ' ')));
+    }
+
+    #[test]
+    fn mixed_streams_pick_their_own_directions() {
+        let mixed = "down:0.5,up:0.5".parse().unwrap();
+        let options = Options::default().seed(7).spawn_window(0).direction(mixed);
+        let mut matrix = Matrix::new(24, 80, options.clone().stream_speed(30.0, 60.0));
+        let ups = |matrix: &Matrix| {
+            matrix
+                .streams
+                .iter()
+                .filter(|s| s.direction() == &Direction::Up)
+                .count()
+        };
+        let initial = ups(&matrix);
+        assert!(initial > 5 && initial < 35, "{initial}");
+
+        // Each stream spawns off the edge behind it, and picks a new direction when regenerated
+        for stream in matrix.streams.iter() {
+            let y = stream.entities[0].y;
+            assert_eq!(
+                y,
+                if stream.direction() == &Direction::Up {
+                    24.0
+                } else {
+                    -1.0
+                }
+            );
+        }
+        for _ in 0..120 {
+            matrix.step(1.0 / 60.0);
+        }
+        assert_ne!(ups(&matrix), initial);
+
+        // The streams that come back are staggered within the spawn window
+        let mut matrix = Matrix::new(24, 80, options.spawn_window(10));
+        for stream in matrix.streams.iter_mut() {
+            stream.entities.iter_mut().for_each(|e| e.x = -100.0);
+        }
+        matrix.step(0.0);
+        let offsets: Vec<f32> = matrix
+            .streams
+            .iter()
+            .map(|s| match s.direction() {
+                Direction::Up => s.entities[0].y - 24.0,
+                _ => -1.0 - s.entities[0].y,
+            })
+            .collect();
+        assert!(offsets.iter().all(|offset| (0.0..10.0).contains(offset)));
+        assert!(offsets.iter().any(|offset| *offset > 0.0));
+
+        // Streams flowing across the lanes are spread over the rows instead
+        let mixed = "down:0.5,left:0.5".parse().unwrap();
+        let matrix = Matrix::new(24, 80, Options::default().seed(7).direction(mixed));
+        for stream in matrix.streams.iter() {
+            if stream.direction() == &Direction::Left {
+                assert!((0.0..24.0).contains(&stream.entities[0].y));
+            }
+        }
+    }
+
+    #[test]
+    fn gravity_and_wind_move_the_streams() {
+        let options = Options::default().seed(7).stream_speed(10.0, 10.0);
+        let mut matrix = Matrix::new(24, 80, options.gravity(20.0).terminal_velocity(15.0));
+        for _ in 0..60 {
+            matrix.step(1.0 / 60.0);
+        }
+        // Each stream accelerates from 10 cells per second, up to the terminal velocity
+        let speeds: Vec<f32> = matrix
+            .streams
+            .iter()
+            .map(|s| s.entities[0].velocity().1)
+            .collect();
+        assert!(speeds
+            .iter()
+            .all(|&speed| speed > 15.0 - 0.01 && speed <= 15.0 + 0.01));
+
+        // The streams drift with the wind, and respawn once blown off the side of the screen
+        let options = Options::default().seed(7).wind(-200.0, 0.0).wind_gust(0.5);
+        let mut matrix = Matrix::new(24, 80, options);
+        let start = matrix.streams[10].entities[0].x;
+        matrix.step(0.1);
+        let drift = start - matrix.streams[10].entities[0].x;
+        assert!(drift > 10.0 && drift < 30.0, "{drift}");
+        for _ in 0..60 {
+            matrix.step(1.0 / 60.0);
+        }
+        assert!(matrix.streams.iter().all(|s| s.entities[0].x > -20.0));
+    }
+
+    #[test]
+    fn streams_fall_through_an_opposing_wind() {
+        // The wind blowing up is stronger than the streams, until gravity picks them up
+        let options = Options::default()
+            .seed(7)
+            .stream_speed(5.0, 5.0)
+            .spawn_window(0);
+        let options = options.gravity(20.0).wind(0.0, -10.0);
+        let mut matrix = Matrix::new(24, 80, options);
+        let mut seen = vec![false; matrix.streams.len()];
+        for _ in 0..180 {
+            matrix.step(1.0 / 60.0);
+            for (seen, stream) in seen.iter_mut().zip(&matrix.streams) {
+                *seen |= stream.entities.iter().any(|e| e.y >= 0.0 && e.y < 24.0);
+            }
+        }
+        assert!(seen.iter().all(|&seen| seen));
+    }
+
+    #[test]
+    fn different_seeds_render_different_output() {
+        assert_ne!(
+            render_frames(Options::default().seed(1), 120),
+            render_frames(Options::default().seed(2), 120)
+        );
     }
 }
@@ -1,9 +1,9 @@
-use crossterm::cursor;
-use crossterm::style::Print;
-use crossterm::QueueableCommand;
+use rand::Rng;
 
-use crate::config;
-use crate::helpers::{colors, direction::Direction, utils};
+use crate::helpers::{direction::Direction, utils};
+use crate::renderer::Buffer;
+use crate::symbols;
+use crate::Options;
 
 use super::entity::Entity;
 
@@ -21,133 +21,200 @@ pub struct Stream {
     /// Y Position
     y: f32,
 
-    /// Speed
+    /// The lane the stream occupies in the [Matrix](super::Matrix)
+    lane: u16,
+
+    /// The direction of motion of the stream
+    direction: Direction,
+
+    /// Speed (in cells per second)
     speed: f32,
 
     /// Count of [entities](Entity) in the stream
     count: u16,
+
+    /// The acceleration along the direction of motion (in cells per second squared)
+    acceleration: (f32, f32),
+
+    /// The number of degrees the hue of the stream's colors is rotated by
+    hue: f32,
 }
 
 impl Stream {
-    /// Construct new stream
-    pub fn new(x: f32, y: f32, config: &config::Config) -> Self {
+    /// Construct new stream, starting at the given position in the given lane
+    pub fn new(
+        x: f32,
+        y: f32,
+        lane: u16,
+        direction: Direction,
+        options: &Options,
+        rng: &mut impl Rng,
+    ) -> Self {
         let mut stream = Stream {
             entities: Vec::new(),
             x,
             y,
-            speed: 1.0,
+            lane,
+            direction,
+            speed: 60.0,
             count: 10,
+            acceleration: (0.0, 0.0),
+            hue: 0.0,
         };
-        stream.generate_entities(config);
-        return stream;
+        stream.generate_entities(options, rng);
+        stream
+    }
+
+    /// Move the position the stream is generated at. The entities already
+    /// in-flight carry on, and the stream respawns here once they're off-screen.
+    pub fn set_origin(&mut self, x: f32, y: f32) {
+        self.x = x;
+        self.y = y;
+    }
+
+    /// Returns the direction of motion of the stream
+    pub fn direction(&self) -> &Direction {
+        &self.direction
+    }
+
+    /// Sets the number of degrees the hue of the stream's colors is rotated by
+    pub fn set_hue(&mut self, hue: f32) {
+        self.hue = hue;
+    }
+
+    /// Changes the character set the [entities](Entity) pick their symbols from
+    pub fn set_mode(&mut self, mode: &symbols::Symbols) {
+        for entity in self.entities.iter_mut() {
+            entity.set_mode(mode);
+        }
     }
 
     /// Generate the entities that constitute the stream
-    pub fn generate_entities(&mut self, config: &config::Config) {
+    pub fn generate_entities(&mut self, options: &Options, rng: &mut impl Rng) {
         // Empty the entities vector
         self.entities.clear();
 
-        // Randomize the speed
-        self.speed = utils::random_between(0.125, 1.0);
+        // Randomize the speed (in cells per second)
+        self.speed = options.speed_distribution.sample(
+            rng,
+            options.stream_min_speed,
+            options.stream_max_speed,
+        );
 
         // Randomize the count
-        self.count = utils::random_between(config.stream_min_count, config.stream_max_count);
+        self.count = utils::random_between(rng, options.stream_min_count, options.stream_max_count);
 
         // Determine the speed based on the direction of motion
-        let (speed_x, speed_y) = match config.direction {
-            Direction::Down => (0.0, self.speed),
-            Direction::Up => (0.0, -self.speed),
-            Direction::Right => (self.speed, 0.0),
-            Direction::Left => (-self.speed, 0.0),
-            Direction::DiagonalRight => (self.speed, self.speed),
-            Direction::DiagonalRightReverse => (-self.speed, -self.speed),
-            Direction::DiagonalLeft => (-self.speed, self.speed),
-            Direction::DiagonalLeftReverse => (self.speed, -self.speed),
+        let (dx, dy) = self.direction.vector();
+        let (speed_x, speed_y) = (dx * self.speed, dy * self.speed);
+
+        // Randomize the pull of gravity on the stream, along its direction of motion
+        self.acceleration = match options.gravity {
+            0.0 => (0.0, 0.0),
+            gravity => {
+                let gravity = gravity * utils::random_between(rng, 0.5, 1.5);
+                let speed = speed_x.hypot(speed_y).max(f32::EPSILON);
+                (gravity * speed_x / speed, gravity * speed_y / speed)
+            }
         };
 
         // Create the leading entity
-        self.entities.push(Entity::new(
+        let mut leader = Entity::new(
             self.x,
             self.y,
             speed_x,
             speed_y,
-            config.leading_entity_color,
-            config,
-        ));
+            options.leading_entity_color,
+            options,
+            rng,
+        );
+        leader.set_symbol(rng);
+        self.entities.push(leader);
 
         // Create the color gradient for the stream
-        let gradient = colors::LinearGradient::new(
-            colors::RGBColor::from(config.stream_color),
-            colors::RGBColor::from(config.stream_color) * config.stream_color_gradient_factor, // Overloaded Operator for Scalar Multiplication
-        );
+        let gradient = options.gradient();
+
+        // The entities trail behind the leader, one cell apart along the main axis of motion.
+        // Double-width symbols take up two columns, so mostly horizontal trails are spaced out accordingly
+        let step = if dx.abs() > dy.abs() {
+            options.mode.width() as f32
+        } else {
+            1.0
+        };
 
         // Create the following entities
         for i in 1..self.count {
             // Determine the color of the entity based on the gradient
             let color = gradient.interpolate(i as f32 / self.count as f32);
 
-            // Determine the entity starting x and y positions based on the direction of flow
-            let (x, y) = match config.direction {
-                Direction::Down => (self.x, self.y - i as f32),
-                Direction::Up => (self.x, self.y + i as f32),
-                Direction::Right => (self.x - i as f32, self.y),
-                Direction::Left => (self.x + i as f32, self.y),
-                Direction::DiagonalRight => (self.x - i as f32, self.y - i as f32),
-                Direction::DiagonalRightReverse => (self.x + i as f32, self.y + i as f32),
-                Direction::DiagonalLeft => (self.x + i as f32, self.y - i as f32),
-                Direction::DiagonalLeftReverse => (self.x - i as f32, self.y + i as f32),
-            };
+            // Determine the entity starting x and y positions, behind the leader
+            let distance = i as f32 * step;
+            let (x, y) = (self.x - dx * distance, self.y - dy * distance);
 
             // Create the entity and add it to the entities vector
-            let mut e = Entity::new(x, y, speed_x, speed_y, color, config);
-            e.set_symbol();
+            let mut e = Entity::new(x, y, speed_x, speed_y, color, options, rng);
+            e.set_symbol(rng);
             self.entities.push(e);
         }
     }
 
-    /// Render the stream
+    /// Determine whether the stream has gone off-screen for good, which is when all of
+    /// its entities are past the same edge of the screen, moving away from it (with the wind).
+    /// The edges the stream spawns behind don't count, as a stream held back there by an opposing
+    /// wind would otherwise be regenerated every frame, and never pick up the speed to come on screen.
+    fn is_off_screen(&self, rows: u16, columns: u16, wind: (f32, f32)) -> bool {
+        let Some(leader) = self.entities.first() else {
+            return false;
+        };
+        let (speed_x, speed_y) = leader.velocity();
+        let (vx, vy) = (speed_x + wind.0, speed_y + wind.1);
+        let (dx, dy) = self.direction.vector();
+        let (rows, columns) = (rows as f32, columns as f32);
+        let entities = &self.entities;
+
+        (dx <= 0.0 && vx <= 0.0 && entities.iter().all(|e| e.x < 0.0))
+            || (dx >= 0.0 && vx >= 0.0 && entities.iter().all(|e| e.x >= columns))
+            || (dy <= 0.0 && vy <= 0.0 && entities.iter().all(|e| e.y < 0.0))
+            || (dy >= 0.0 && vy >= 0.0 && entities.iter().all(|e| e.y >= rows))
+    }
+
+    /// Render the stream into the frame [Buffer], after moving it for the given number of seconds.
+    /// The hues of the colors are rotated by the given number of degrees, on top of the stream's own hue,
+    /// and the stream is blown along by the wind (in cells per second).
     pub fn render(
         &mut self,
-        rows: i32,
-        columns: i32,
-        config: &config::Config,
-        stdout: &mut std::io::Stdout,
-    ) -> std::io::Result<()> {
-        // Check the last entity of the stream ...
-        if let Some(e) = self.entities.last() {
-            // Clean up the last entity. As the stream moves down, all entities will be overwritten
-            // by the next frame, except for the trailing entity. So we manually overwrite it so that
-            // the stream doesn't leave a trail.
-            if !config.leave_trail {
-                stdout
-                    .queue(cursor::MoveTo(e.x as u16, e.y as u16))?
-                    .queue(Print(" "))?;
-            }
-
-            // This is also a good time to check if the last entity is off the screen,
-            // and if it is, we regenerate the stream and place it back at the top.
-            let should_regenerate = match config.direction {
-                Direction::Down => e.y >= rows as f32,
-                Direction::Up => e.y < 0.0,
-                Direction::Right => e.x >= columns as f32,
-                Direction::Left => e.x < 0.0,
-                Direction::DiagonalLeft => e.x < 0.0 && e.y >= rows as f32,
-                Direction::DiagonalLeftReverse => e.x >= columns as f32 && e.y < 0.0,
-                Direction::DiagonalRight => e.x >= columns as f32 && e.y >= rows as f32,
-                Direction::DiagonalRightReverse => e.x < 0.0 && e.y < 0.0,
-            };
-
-            if should_regenerate {
-                self.generate_entities(config);
+        options: &Options,
+        buffer: &mut Buffer,
+        dt: f32,
+        hue: f32,
+        wind: (f32, f32),
+        rng: &mut impl Rng,
+    ) {
+        // Once the stream is off the screen, we regenerate it and place it back at the start
+        let (rows, columns) = (buffer.rows(), buffer.columns());
+        if self.is_off_screen(rows, columns, wind) {
+            // When the directions are mixed, the stream picks a new one, and starts off the edge behind it
+            if let Direction::Mixed(_) = options.direction {
+                self.direction = options.direction.choose(rng);
+                let offset = super::spawn_offset(options.spawn_window, rng);
+                (self.x, self.y) = super::spawn_position(
+                    &options.direction,
+                    &self.direction,
+                    self.lane,
+                    rows,
+                    columns,
+                    offset,
+                );
             }
+            self.generate_entities(options, rng);
         }
 
-        // Move the stream down and render each entity
+        // Move the stream along and render each entity
+        let hue = (self.hue + hue) % 360.0;
         for entity in self.entities.iter_mut() {
-            entity.rain();
-            entity.render(rows, columns, stdout)?;
+            entity.accelerate(self.acceleration, options.terminal_velocity, dt);
+            entity.rain(dt, wind);
+            entity.render(buffer, hue, rng);
         }
-
-        Ok(())
     }
 }
@@ -0,0 +1,380 @@
+use crate::helpers::{colors, direction, distribution};
+use crate::symbols;
+
+//  =======
+//  OPTIONS
+//  =======
+
+/// The options that describe the look and behavior of the [Matrix](crate::Matrix).
+///
+/// Starts from the [default](Options::default) look, and can be customized using
+/// the builder methods:
+///
+/// ```
+/// use matrix_rain::{Options, RGBColor, Symbols};
+///
+/// let options = Options::default()
+///     .mode(Symbols::Binary)
+///     .stream_color(RGBColor(255, 176, 0))
+///     .leave_trail(true);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Options {
+    /// The character symbol set to use
+    pub mode: symbols::Symbols,
+    /// The color of the streaming entities
+    pub stream_color: colors::RGBColor,
+    /// The multiplier that describes the extent of the gradient in the stream color
+    pub stream_color_gradient_factor: f32,
+    /// The gradient the streams fade along. Takes precedence over the `stream_color` and its gradient factor
+    pub stream_gradient: Option<colors::LinearGradient>,
+    /// The color space the stream gradient is interpolated in
+    pub gradient_interpolation: colors::Interpolation,
+    /// Color of the leading entity in a stream
+    pub leading_entity_color: colors::RGBColor,
+    /// The background color behind the streams. `None` uses the terminal's default background
+    pub background_color: Option<colors::RGBColor>,
+    /// The gradient the background fades along from top to bottom. Takes precedence over the `background_color`
+    pub background_gradient: Option<colors::LinearGradient>,
+    /// Gives each stream a different hue, based on its lane, spreading the colors of the rainbow across the screen
+    pub rainbow: bool,
+    /// The number of degrees per second the hues of the streams rotate by. Zero keeps them still
+    pub hue_cycle: f32,
+    /// Leaves the trail intact
+    pub leave_trail: bool,
+    /// The number of frames per second the matrix is rendered at
+    pub fps: u16,
+    /// Minimum number of entities per stream
+    pub stream_min_count: u16,
+    /// Maximum number of entities per stream
+    pub stream_max_count: u16,
+    /// Minimum speed of the streams, in cells per second
+    pub stream_min_speed: f32,
+    /// Maximum speed of the streams, in cells per second
+    pub stream_max_speed: f32,
+    /// How the speeds of the streams are distributed between the minimum and maximum
+    pub speed_distribution: distribution::Distribution,
+    /// The maximum distance, in cells, off-screen that the streams start at. Staggers their first appearance
+    pub spawn_window: u16,
+    /// The acceleration of the streams along their direction of motion, in cells per second squared.
+    /// Each stream is pulled by a random share of it, between half and one and a half times as much
+    pub gravity: f32,
+    /// The maximum speed of the streams, in cells per second. Zero leaves their speed unlimited
+    pub terminal_velocity: f32,
+    /// The wind that blows the streams along, as (x, y) in cells per second
+    pub wind: (f32, f32),
+    /// How much the strength of the wind varies in gusts, as a share of the wind
+    pub wind_gust: f32,
+    /// The spacing between the streams
+    pub stream_spacing: u16,
+    /// The max number-of-seconds within which an entity randomly switches it's symbol
+    pub switch_interval: u16,
+    /// The direction of motion for the stream particles
+    pub direction: direction::Direction,
+    /// The seed for the random number generator. Identical seeds and sizes produce identical output
+    pub seed: Option<u64>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            mode: symbols::Symbols::Original,
+            stream_color: colors::RGBColor(0, 255, 70),
+            stream_color_gradient_factor: 0.33,
+            stream_gradient: None,
+            gradient_interpolation: colors::Interpolation::Srgb,
+            leading_entity_color: colors::RGBColor(200, 255, 200),
+            background_color: None,
+            background_gradient: None,
+            rainbow: false,
+            hue_cycle: 0.0,
+            leave_trail: false,
+            fps: 60,
+            stream_min_count: 5,
+            stream_max_count: 25,
+            stream_min_speed: 7.5,
+            stream_max_speed: 60.0,
+            speed_distribution: distribution::Distribution::Uniform,
+            spawn_window: 50,
+            gravity: 0.0,
+            terminal_velocity: 0.0,
+            wind: (0.0, 0.0),
+            wind_gust: 0.0,
+            stream_spacing: 2,
+            switch_interval: 1,
+            direction: direction::Direction::Down,
+            seed: None,
+        }
+    }
+}
+
+impl Options {
+    /// Sets the character symbol set to use
+    pub fn mode(mut self, mode: symbols::Symbols) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the color of the streaming entities
+    pub fn stream_color(mut self, color: colors::RGBColor) -> Self {
+        self.stream_color = color;
+        self
+    }
+
+    /// Sets the multiplier that describes the extent of the gradient in the stream color
+    pub fn stream_color_gradient_factor(mut self, factor: f32) -> Self {
+        self.stream_color_gradient_factor = factor;
+        self
+    }
+
+    /// Sets the gradient the streams fade along, in place of the `stream_color` and its gradient factor
+    pub fn stream_gradient(mut self, gradient: colors::LinearGradient) -> Self {
+        self.stream_gradient = Some(gradient);
+        self
+    }
+
+    /// Sets the color space the stream gradient is interpolated in
+    pub fn gradient_interpolation(mut self, interpolation: colors::Interpolation) -> Self {
+        self.gradient_interpolation = interpolation;
+        self
+    }
+
+    /// Sets the color of the leading entity in a stream
+    pub fn leading_entity_color(mut self, color: colors::RGBColor) -> Self {
+        self.leading_entity_color = color;
+        self
+    }
+
+    /// Sets the background color behind the streams
+    pub fn background_color(mut self, color: colors::RGBColor) -> Self {
+        self.background_color = Some(color);
+        self
+    }
+
+    /// Sets the gradient the background fades along from top to bottom, in place of the `background_color`
+    pub fn background_gradient(mut self, gradient: colors::LinearGradient) -> Self {
+        self.background_gradient = Some(gradient);
+        self
+    }
+
+    /// Sets whether each stream gets a different hue, based on its lane
+    pub fn rainbow(mut self, rainbow: bool) -> Self {
+        self.rainbow = rainbow;
+        self
+    }
+
+    /// Sets the number of degrees per second the hues of the streams rotate by
+    pub fn hue_cycle(mut self, degrees_per_second: f32) -> Self {
+        self.hue_cycle = degrees_per_second;
+        self
+    }
+
+    /// Sets whether the streams leave their trail intact
+    pub fn leave_trail(mut self, leave_trail: bool) -> Self {
+        self.leave_trail = leave_trail;
+        self
+    }
+
+    /// Sets the number of frames per second the matrix is rendered at
+    pub fn fps(mut self, fps: u16) -> Self {
+        self.fps = fps;
+        self
+    }
+
+    /// Sets the minimum and maximum number of entities per stream
+    pub fn stream_count(mut self, min: u16, max: u16) -> Self {
+        self.stream_min_count = min;
+        self.stream_max_count = max;
+        self
+    }
+
+    /// Sets the minimum and maximum speed of the streams, in cells per second
+    pub fn stream_speed(mut self, min: f32, max: f32) -> Self {
+        self.stream_min_speed = min;
+        self.stream_max_speed = max;
+        self
+    }
+
+    /// Sets how the speeds of the streams are distributed between the minimum and maximum
+    pub fn speed_distribution(mut self, distribution: distribution::Distribution) -> Self {
+        self.speed_distribution = distribution;
+        self
+    }
+
+    /// Sets the maximum distance, in cells, off-screen that the streams start at
+    pub fn spawn_window(mut self, cells: u16) -> Self {
+        self.spawn_window = cells;
+        self
+    }
+
+    /// Sets the acceleration of the streams along their direction of motion, in cells per second squared
+    pub fn gravity(mut self, gravity: f32) -> Self {
+        self.gravity = gravity;
+        self
+    }
+
+    /// Sets the maximum speed of the streams, in cells per second. Zero leaves their speed unlimited
+    pub fn terminal_velocity(mut self, speed: f32) -> Self {
+        self.terminal_velocity = speed;
+        self
+    }
+
+    /// Sets the wind that blows the streams along, in cells per second
+    pub fn wind(mut self, x: f32, y: f32) -> Self {
+        self.wind = (x, y);
+        self
+    }
+
+    /// Sets how much the strength of the wind varies in gusts, as a share of the wind
+    pub fn wind_gust(mut self, gust: f32) -> Self {
+        self.wind_gust = gust;
+        self
+    }
+
+    /// Sets the spacing between the streams
+    pub fn stream_spacing(mut self, spacing: u16) -> Self {
+        self.stream_spacing = spacing;
+        self
+    }
+
+    /// Sets the max number-of-seconds within which an entity randomly switches it's symbol
+    pub fn switch_interval(mut self, seconds: u16) -> Self {
+        self.switch_interval = seconds;
+        self
+    }
+
+    /// Sets the direction of motion for the stream particles
+    pub fn direction(mut self, direction: direction::Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Sets the seed for the random number generator, making the output reproducible
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl Options {
+    /// Checks that the values of the options make sense together.
+    /// [Matrix::try_new](crate::Matrix::try_new) refuses options that don't.
+    pub fn validate(&self) -> Result<(), InvalidOptionsError> {
+        let invalid = |reason: String| Err(InvalidOptionsError::from(reason));
+
+        if self.stream_min_count >= self.stream_max_count {
+            return invalid(format!(
+                "stream_min_count ({}) must be less than stream_max_count ({})",
+                self.stream_min_count, self.stream_max_count
+            ));
+        }
+        if !(self.stream_min_speed.is_finite() && self.stream_min_speed > 0.0) {
+            return invalid(format!(
+                "stream_min_speed ({}) must be greater than zero",
+                self.stream_min_speed
+            ));
+        }
+        if !self.stream_max_speed.is_finite() || self.stream_min_speed > self.stream_max_speed {
+            return invalid(format!(
+                "stream_min_speed ({}) must not be greater than stream_max_speed ({})",
+                self.stream_min_speed, self.stream_max_speed
+            ));
+        }
+        if !(self.gravity.is_finite() && self.wind.0.is_finite() && self.wind.1.is_finite()) {
+            return invalid("gravity and wind must be finite".into());
+        }
+        if !(self.terminal_velocity.is_finite() && self.terminal_velocity >= 0.0) {
+            return invalid(format!(
+                "terminal_velocity ({}) must not be negative",
+                self.terminal_velocity
+            ));
+        }
+        if !(self.wind_gust.is_finite() && self.wind_gust >= 0.0) {
+            return invalid(format!(
+                "wind_gust ({}) must not be negative",
+                self.wind_gust
+            ));
+        }
+        if self.stream_spacing == 0 {
+            return invalid("stream_spacing must be at least 1".into());
+        }
+        if self.fps == 0 {
+            return invalid("fps must be at least 1".into());
+        }
+        if !self.hue_cycle.is_finite() {
+            return invalid(format!("hue_cycle ({}) must be finite", self.hue_cycle));
+        }
+        if matches!(&self.mode, symbols::Symbols::Custom(s) if s.is_empty()) {
+            return invalid("a custom mode needs at least one symbol".into());
+        }
+        Ok(())
+    }
+
+    /// Returns the gradient the streams fade along. Unless a `stream_gradient` was set,
+    /// fades from the `stream_color` to the `stream_color` scaled by the gradient factor.
+    pub fn gradient(&self) -> colors::LinearGradient {
+        let gradient = match &self.stream_gradient {
+            Some(gradient) => gradient.clone(),
+            None => colors::LinearGradient::new(
+                self.stream_color,
+                self.stream_color * self.stream_color_gradient_factor, // Overloaded Operator for Scalar Multiplication
+            ),
+        };
+        gradient.with_interpolation(self.gradient_interpolation)
+    }
+}
+
+// ERROR
+// -----
+
+/// The error returned when the [Options] don't make sense together
+#[derive(Debug)]
+pub struct InvalidOptionsError {
+    reason: String,
+}
+
+impl From<String> for InvalidOptionsError {
+    fn from(reason: String) -> Self {
+        Self { reason }
+    }
+}
+
+impl std::fmt::Display for InvalidOptionsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid options: {}", self.reason)
+    }
+}
+
+impl std::error::Error for InvalidOptionsError {}
+
+// -----
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_validate_the_options() {
+        assert!(Options::default().validate().is_ok());
+        assert!(Options::default().stream_count(5, 5).validate().is_err());
+        assert!(Options::default()
+            .stream_speed(0.0, 10.0)
+            .validate()
+            .is_err());
+        assert!(Options::default()
+            .stream_speed(20.0, 10.0)
+            .validate()
+            .is_err());
+        assert!(Options::default().wind(f32::NAN, 0.0).validate().is_err());
+        assert!(Options::default().stream_spacing(0).validate().is_err());
+        assert!(Options::default().fps(0).validate().is_err());
+        assert!(Options::default()
+            .hue_cycle(f32::INFINITY)
+            .validate()
+            .is_err());
+        let empty = symbols::Symbols::Custom(String::new());
+        assert!(Options::default().mode(empty).validate().is_err());
+    }
+}
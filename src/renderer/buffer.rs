@@ -0,0 +1,306 @@
+use unicode_width::UnicodeWidthChar;
+
+use crate::helpers::colors;
+
+//  ====
+//  CELL
+//  ====
+
+/// A single character cell on the screen
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cell {
+    /// The character symbol to display
+    pub symbol: char,
+    /// The foreground color. `None` uses the terminal default
+    pub fg: Option<colors::RGBColor>,
+    /// The background color. `None` uses the terminal default
+    pub bg: Option<colors::RGBColor>,
+}
+
+impl Default for Cell {
+    /// An empty cell with the terminal's default colors
+    fn default() -> Self {
+        Self {
+            symbol: ' ',
+            fg: None,
+            bg: None,
+        }
+    }
+}
+
+impl Cell {
+    /// The symbol of the cells covered by the right half of a double-width symbol
+    const CONTINUATION: char = '\0';
+
+    /// Constructs a new [Cell] with the given symbol and foreground color
+    pub fn new(symbol: char, fg: colors::RGBColor) -> Self {
+        Self {
+            symbol,
+            fg: Some(fg),
+            bg: None,
+        }
+    }
+
+    /// Returns the number of columns the symbol occupies on the terminal.
+    /// Double-width symbols (e.g. Katakana or emoji) occupy two columns.
+    pub fn width(&self) -> u16 {
+        if self.is_continuation() {
+            return 0;
+        }
+        self.symbol.width().unwrap_or(1).max(1) as u16
+    }
+
+    /// Whether this cell is covered by the double-width symbol in the cell to its left.
+    /// Such cells have nothing of their own to draw.
+    pub fn is_continuation(&self) -> bool {
+        self.symbol == Self::CONTINUATION
+    }
+
+    /// Returns the cell covered by the right half of this cell's double-width symbol
+    fn continuation(&self) -> Self {
+        Self {
+            symbol: Self::CONTINUATION,
+            ..*self
+        }
+    }
+}
+
+//  ======
+//  BUFFER
+//  ======
+
+/// A grid of [cells](Cell) that represents a single frame
+#[derive(Clone, Debug, PartialEq)]
+pub struct Buffer {
+    /// Number of rows
+    rows: u16,
+    /// Number of columns
+    columns: u16,
+    /// The cells in row-major order
+    cells: Vec<Cell>,
+    /// The background color of each row, that cells without a background color of their own take on
+    backgrounds: Vec<Option<colors::RGBColor>>,
+}
+
+impl Buffer {
+    /// Constructs a new [Buffer] filled with empty cells
+    pub fn new(rows: u16, columns: u16) -> Self {
+        Self {
+            rows,
+            columns,
+            cells: vec![Cell::default(); rows as usize * columns as usize],
+            backgrounds: vec![None; rows as usize],
+        }
+    }
+
+    /// Returns the number of rows in the [Buffer]
+    pub fn rows(&self) -> u16 {
+        self.rows
+    }
+
+    /// Returns the number of columns in the [Buffer]
+    pub fn columns(&self) -> u16 {
+        self.columns
+    }
+
+    /// Returns the index of the cell at the given position, if it lies within the [Buffer]
+    fn index(&self, x: u16, y: u16) -> Option<usize> {
+        if x < self.columns && y < self.rows {
+            Some(y as usize * self.columns as usize + x as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the [Cell] at the given position
+    pub fn get(&self, x: u16, y: u16) -> Option<&Cell> {
+        self.index(x, y).map(|i| &self.cells[i])
+    }
+
+    /// Sets the background color of the given row. All of the cells in the row take on the color,
+    /// as do the cells that are set or cleared later on without a background color of their own.
+    pub fn set_row_background(&mut self, y: u16, color: Option<colors::RGBColor>) {
+        if y >= self.rows {
+            return;
+        }
+        self.backgrounds[y as usize] = color;
+        let start = y as usize * self.columns as usize;
+        for cell in &mut self.cells[start..start + self.columns as usize] {
+            cell.bg = color;
+        }
+    }
+
+    /// Sets the [Cell] at the given position. Positions outside the [Buffer] are ignored.
+    /// Cells without a background color take on the background color of the row.
+    ///
+    /// Double-width symbols also cover the cell to their right. Any double-width symbol
+    /// that is partially overwritten is erased entirely, so that no stale halves remain.
+    /// A double-width symbol that doesn't fit in the last column is replaced by a blank.
+    pub fn set(&mut self, x: u16, y: u16, mut cell: Cell) {
+        let Some(i) = self.index(x, y) else {
+            return;
+        };
+        cell.bg = cell.bg.or(self.backgrounds[y as usize]);
+
+        self.erase_overlap(x, y);
+        if cell.width() > 1 {
+            if x + 1 < self.columns {
+                self.erase_overlap(x + 1, y);
+                self.cells[i + 1] = cell.continuation();
+            } else {
+                cell.symbol = ' ';
+            }
+        }
+        self.cells[i] = cell;
+    }
+
+    /// Erases the double-width symbol the cell at the given position is a part of, if any
+    fn erase_overlap(&mut self, x: u16, y: u16) {
+        let Some(i) = self.index(x, y) else {
+            return;
+        };
+
+        let cell = self.cells[i];
+        if cell.is_continuation() && x > 0 {
+            self.cells[i - 1].symbol = ' ';
+        } else if cell.width() > 1 && x + 1 < self.columns {
+            self.cells[i + 1].symbol = ' ';
+        }
+        self.cells[i].symbol = ' ';
+    }
+
+    /// Writes the text into the [Buffer], starting at the given position.
+    /// Characters that fall outside the [Buffer] are ignored.
+    pub fn print(&mut self, x: u16, y: u16, text: &str, fg: colors::RGBColor) {
+        let mut x = x;
+        for symbol in text.chars() {
+            let cell = Cell::new(symbol, fg);
+            self.set(x, y, cell);
+            x = x.saturating_add(cell.width());
+        }
+    }
+
+    /// Resizes the [Buffer] to the given number of rows and columns.
+    /// Cells that still fit are kept, and any new cells are empty.
+    pub fn resize(&mut self, rows: u16, columns: u16) {
+        let mut resized = Buffer::new(rows, columns);
+        for y in 0..rows.min(self.rows) {
+            for x in 0..columns.min(self.columns) {
+                match self.get(x, y) {
+                    // The continuation cells are restored along with their double-width symbol
+                    Some(cell) if !cell.is_continuation() => resized.set(x, y, *cell),
+                    _ => {}
+                }
+            }
+            resized.backgrounds[y as usize] = self.backgrounds[y as usize];
+        }
+        *self = resized;
+    }
+
+    /// Resets every cell in the [Buffer] to an empty cell, with the background color of its row
+    pub fn clear(&mut self) {
+        let columns = self.columns as usize;
+        for (row, bg) in self.cells.chunks_mut(columns.max(1)).zip(&self.backgrounds) {
+            row.fill(Cell {
+                bg: *bg,
+                ..Cell::default()
+            });
+        }
+    }
+
+    /// Returns an iterator over the cells that differ from the `previous` frame,
+    /// along with their x and y positions, in row-major order.
+    pub fn diff<'a>(&'a self, previous: &'a Buffer) -> impl Iterator<Item = (u16, u16, &'a Cell)> {
+        let columns = self.columns as usize;
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(move |(i, cell)| previous.cells.get(*i) != Some(*cell))
+            .map(move |(i, cell)| ((i % columns) as u16, (i / columns) as u16, cell))
+    }
+}
+
+// -----
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_ignore_out_of_bounds_positions() {
+        let mut buffer = Buffer::new(2, 3);
+        buffer.set(3, 0, Cell::new('a', colors::RGBColor(0, 255, 0)));
+        buffer.set(0, 2, Cell::new('a', colors::RGBColor(0, 255, 0)));
+        assert_eq!(buffer, Buffer::new(2, 3));
+        assert_eq!(buffer.get(3, 0), None);
+    }
+
+    #[test]
+    fn should_keep_cells_that_fit_when_resizing() {
+        let cell = Cell::new('a', colors::RGBColor(0, 255, 0));
+        let mut buffer = Buffer::new(2, 3);
+        buffer.set(0, 0, cell);
+        buffer.set(2, 1, cell);
+        buffer.resize(4, 2);
+        assert_eq!((buffer.rows(), buffer.columns()), (4, 2));
+        assert_eq!(buffer.get(0, 0), Some(&cell));
+        assert_eq!(buffer.get(1, 1), Some(&Cell::default()));
+        assert_eq!(buffer.get(1, 3), Some(&Cell::default()));
+    }
+
+    #[test]
+    fn should_erase_both_halves_of_double_width_symbols() {
+        let green = colors::RGBColor(0, 255, 0);
+        let mut buffer = Buffer::new(1, 5);
+        buffer.set(1, 0, Cell::new('ア', green));
+        assert_eq!(buffer.get(1, 0).map(Cell::width), Some(2));
+        assert!(buffer.get(2, 0).is_some_and(Cell::is_continuation));
+
+        // Overwriting the right half erases the left half
+        buffer.set(2, 0, Cell::new('a', green));
+        assert_eq!(buffer.get(1, 0).map(|c| c.symbol), Some(' '));
+        assert_eq!(buffer.get(2, 0).map(|c| c.symbol), Some('a'));
+
+        // Overwriting a double-width symbol with another one shifted right erases the first
+        buffer.set(0, 0, Cell::new('カ', green));
+        buffer.set(1, 0, Cell::new('サ', green));
+        assert_eq!(buffer.get(0, 0).map(|c| c.symbol), Some(' '));
+        assert_eq!(buffer.get(1, 0).map(|c| c.symbol), Some('サ'));
+
+        // Double-width symbols don't fit in the last column
+        buffer.set(4, 0, Cell::new('ナ', green));
+        assert_eq!(buffer.get(4, 0).map(|c| c.symbol), Some(' '));
+    }
+
+    #[test]
+    fn should_fill_cells_with_the_row_background() {
+        let (green, black) = (colors::RGBColor(0, 255, 0), colors::RGBColor(0, 10, 0));
+        let mut buffer = Buffer::new(2, 2);
+        buffer.set(0, 0, Cell::new('a', green));
+        buffer.set_row_background(0, Some(black));
+        assert_eq!(buffer.get(0, 0).and_then(|c| c.bg), Some(black));
+        assert_eq!(buffer.get(1, 0).and_then(|c| c.bg), Some(black));
+        assert_eq!(buffer.get(0, 1).and_then(|c| c.bg), None);
+
+        buffer.clear();
+        assert_eq!(
+            buffer.get(0, 0).map(|c| (c.symbol, c.bg)),
+            Some((' ', Some(black)))
+        );
+        buffer.set(1, 0, Cell::new('b', green));
+        assert_eq!(buffer.get(1, 0).and_then(|c| c.bg), Some(black));
+    }
+
+    #[test]
+    fn should_only_yield_changed_cells() {
+        let previous = Buffer::new(2, 3);
+        let mut current = previous.clone();
+        let cell = Cell::new('a', colors::RGBColor(0, 255, 0));
+        current.set(2, 0, cell);
+        current.set(1, 1, cell);
+        let changes: Vec<_> = current.diff(&previous).collect();
+        assert_eq!(changes, vec![(2, 0, &cell), (1, 1, &cell)]);
+    }
+}
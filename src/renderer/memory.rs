@@ -0,0 +1,85 @@
+use super::{Backend, Buffer, Cell};
+
+//  ==============
+//  MEMORY BACKEND
+//  ==============
+
+/// A [Backend] that draws into an in-memory [Buffer] instead of a terminal.
+/// Useful for tests and for reading out the rendered cells.
+pub struct MemoryBackend {
+    /// The cells drawn so far
+    buffer: Buffer,
+}
+
+impl MemoryBackend {
+    /// Constructs a new [MemoryBackend] of the given size
+    pub fn new(rows: u16, columns: u16) -> Self {
+        Self {
+            buffer: Buffer::new(rows, columns),
+        }
+    }
+
+    /// Returns the [Buffer] holding the cells drawn so far
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+}
+
+impl Backend for MemoryBackend {
+    fn draw<'a, I>(&mut self, cells: I) -> std::io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        for (x, y, cell) in cells {
+            // The continuation cells are filled in along with their double-width symbol
+            if !cell.is_continuation() {
+                self.buffer.set(x, y, *cell);
+            }
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self) -> std::io::Result<()> {
+        self.buffer.clear();
+        Ok(())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    fn size(&self) -> std::io::Result<(u16, u16)> {
+        Ok((self.buffer.columns(), self.buffer.rows()))
+    }
+}
+
+// -----
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::colors;
+
+    #[test]
+    fn should_hold_the_drawn_cells() {
+        let mut frame = Buffer::new(2, 3);
+        frame.set(2, 1, Cell::new('a', colors::RGBColor(0, 255, 0)));
+        let mut backend = MemoryBackend::new(2, 3);
+        backend.draw(frame.diff(&Buffer::new(2, 3))).unwrap();
+        assert_eq!(backend.buffer(), &frame);
+        assert_eq!(backend.size().unwrap(), (3, 2));
+    }
+
+    #[test]
+    fn should_hold_double_width_symbols() {
+        let mut frame = Buffer::new(2, 3);
+        frame.set(0, 0, Cell::new('ア', colors::RGBColor(0, 255, 0)));
+        frame.set(1, 1, Cell::new('😀', colors::RGBColor(0, 84, 23)));
+        let mut backend = MemoryBackend::new(2, 3);
+        backend.draw(frame.diff(&Buffer::new(2, 3))).unwrap();
+        assert_eq!(backend.buffer(), &frame);
+        assert_eq!(backend.buffer().get(0, 0).unwrap().symbol, 'ア');
+    }
+}
@@ -0,0 +1,31 @@
+mod buffer;
+mod memory;
+mod style;
+mod terminal;
+
+pub use buffer::{Buffer, Cell};
+pub use memory::MemoryBackend;
+pub use terminal::CrosstermBackend;
+
+pub(crate) use style::{to_color, SetBackground, SetForeground};
+
+//  =======
+//  BACKEND
+//  =======
+
+/// A surface that the [Matrix](crate::matrix::Matrix) draws its frames onto
+pub trait Backend {
+    /// Draws the given cells at their x and y positions
+    fn draw<'a, I>(&mut self, cells: I) -> std::io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>;
+
+    /// Clears the entire surface
+    fn clear(&mut self) -> std::io::Result<()>;
+
+    /// Flushes any pending output to the surface
+    fn flush(&mut self) -> std::io::Result<()>;
+
+    /// Returns the size of the surface as `(columns, rows)`
+    fn size(&self) -> std::io::Result<(u16, u16)>;
+}
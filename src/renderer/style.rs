@@ -0,0 +1,94 @@
+use crossterm::style::{Color, SetBackgroundColor, SetForegroundColor};
+use crossterm::Command;
+
+use crate::helpers::{color_mode::ColorMode, colors};
+
+//  =====
+//  STYLE
+//  =====
+
+/// Converts an optional [RGBColor](colors::RGBColor) into a crossterm [Color] for the given [ColorMode].
+/// The color is quantized to the nearest palette entry for the 256 and 16 color modes,
+/// and always [reset](Color::Reset) when colors are disabled.
+pub(crate) fn to_color(color: Option<colors::RGBColor>, mode: ColorMode) -> Color {
+    let Some(c) = color else {
+        return Color::Reset;
+    };
+    match mode {
+        ColorMode::Auto | ColorMode::TrueColor => Color::Rgb {
+            r: c.r(),
+            g: c.g(),
+            b: c.b(),
+        },
+        ColorMode::Ansi256 => Color::AnsiValue(c.to_ansi256()),
+        ColorMode::Ansi16 => ANSI16_COLORS[c.to_ansi16() as usize],
+        ColorMode::None => Color::Reset,
+    }
+}
+
+/// The crossterm [colors](Color) for the 16 basic ANSI colors, in palette order
+const ANSI16_COLORS: [Color; 16] = [
+    Color::Black,
+    Color::DarkRed,
+    Color::DarkGreen,
+    Color::DarkYellow,
+    Color::DarkBlue,
+    Color::DarkMagenta,
+    Color::DarkCyan,
+    Color::Grey,
+    Color::DarkGrey,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::White,
+];
+
+/// Returns the index of the color in the 16 basic ANSI colors, if it is one
+fn ansi16_index(color: Color) -> Option<u8> {
+    ANSI16_COLORS
+        .iter()
+        .position(|&c| c == color)
+        .map(|i| i as u8)
+}
+
+/// Sets the foreground color, like [SetForegroundColor].
+/// The 16 basic ANSI colors are written with the basic `30-37` and `90-97` codes
+/// rather than the 256 color codes, so that terminals with only 16 colors understand them.
+pub(crate) struct SetForeground(pub Color);
+
+impl Command for SetForeground {
+    fn write_ansi(&self, f: &mut impl std::fmt::Write) -> std::fmt::Result {
+        match ansi16_index(self.0) {
+            Some(i) if i < 8 => write!(f, "\x1b[{}m", 30 + i),
+            Some(i) => write!(f, "\x1b[{}m", 90 + i - 8),
+            None => SetForegroundColor(self.0).write_ansi(f),
+        }
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        SetForegroundColor(self.0).execute_winapi()
+    }
+}
+
+/// Sets the background color, like [SetBackgroundColor].
+/// The 16 basic ANSI colors are written with the basic `40-47` and `100-107` codes.
+pub(crate) struct SetBackground(pub Color);
+
+impl Command for SetBackground {
+    fn write_ansi(&self, f: &mut impl std::fmt::Write) -> std::fmt::Result {
+        match ansi16_index(self.0) {
+            Some(i) if i < 8 => write!(f, "\x1b[{}m", 40 + i),
+            Some(i) => write!(f, "\x1b[{}m", 100 + i - 8),
+            None => SetBackgroundColor(self.0).write_ansi(f),
+        }
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        SetBackgroundColor(self.0).execute_winapi()
+    }
+}
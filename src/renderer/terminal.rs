@@ -0,0 +1,205 @@
+use std::io::Write;
+
+use crossterm::{
+    cursor,
+    style::{Color, Print, ResetColor},
+    terminal, QueueableCommand,
+};
+
+use super::{to_color, Backend, Cell, SetBackground, SetForeground};
+use crate::helpers::color_mode::ColorMode;
+
+//  =================
+//  CROSSTERM BACKEND
+//  =================
+
+/// A [Backend] that writes ANSI escape sequences to any [writer](Write) using crossterm.
+/// Typically wraps [`std::io::Stdout`], but can just as well write into a `Vec<u8>`.
+pub struct CrosstermBackend<W: Write> {
+    /// The writer the escape sequences are written to
+    writer: W,
+    /// The color mode the colors are written in
+    color_mode: ColorMode,
+}
+
+impl<W: Write> CrosstermBackend<W> {
+    /// Constructs a new [CrosstermBackend] that writes to the given writer in truecolor
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            color_mode: ColorMode::TrueColor,
+        }
+    }
+
+    /// Sets the [ColorMode] the colors are written in. [ColorMode::Auto] is resolved from the environment.
+    pub fn with_color_mode(mut self, color_mode: ColorMode) -> Self {
+        self.color_mode = color_mode.resolve();
+        self
+    }
+
+    /// Returns a reference to the underlying writer
+    pub fn writer(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns a mutable reference to the underlying writer
+    pub fn writer_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+}
+
+impl<W: Write> Backend for CrosstermBackend<W> {
+    /// Writes the given cells to the writer.
+    ///
+    /// Consecutive cells on the same row are written as a single run without
+    /// repositioning the cursor, and color codes are only emitted when the color changes.
+    fn draw<'a, I>(&mut self, cells: I) -> std::io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        // Queue everything into a single buffer so that the frame is written out in one go
+        let mut bytes = Vec::new();
+
+        // The position the terminal cursor is known to be at, if any
+        let mut position: Option<(u16, u16)> = None;
+        // The colors currently set on the terminal
+        let mut fg = Color::Reset;
+        let mut bg = Color::Reset;
+
+        for (x, y, cell) in cells {
+            // The right half of a double-width symbol is drawn along with its left half
+            if cell.is_continuation() {
+                continue;
+            }
+
+            // Only move the cursor if this cell doesn't continue the current run
+            if position != Some((x, y)) {
+                bytes.queue(cursor::MoveTo(x, y))?;
+            }
+
+            // Skip the color codes if the colors are unchanged
+            let cell_fg = to_color(cell.fg, self.color_mode);
+            if cell_fg != fg {
+                bytes.queue(SetForeground(cell_fg))?;
+                fg = cell_fg;
+            }
+            let cell_bg = to_color(cell.bg, self.color_mode);
+            if cell_bg != bg {
+                bytes.queue(SetBackground(cell_bg))?;
+                bg = cell_bg;
+            }
+
+            bytes.queue(Print(cell.symbol))?;
+
+            // Double-width symbols move the cursor two columns ahead
+            position = Some((x + cell.width(), y));
+        }
+
+        // Reset the colors so that they don't bleed into anything written afterwards
+        if fg != Color::Reset || bg != Color::Reset {
+            bytes.queue(ResetColor)?;
+        }
+
+        self.writer.write_all(&bytes)
+    }
+
+    fn clear(&mut self) -> std::io::Result<()> {
+        self.writer
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Returns the size of the terminal window
+    fn size(&self) -> std::io::Result<(u16, u16)> {
+        terminal::size()
+    }
+}
+
+// -----
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::colors;
+    use crate::renderer::Buffer;
+
+    fn render(current: &Buffer, previous: &Buffer) -> String {
+        render_in(ColorMode::TrueColor, current, previous)
+    }
+
+    fn render_in(color_mode: ColorMode, current: &Buffer, previous: &Buffer) -> String {
+        let mut backend = CrosstermBackend::new(Vec::new()).with_color_mode(color_mode);
+        backend.draw(current.diff(previous)).unwrap();
+        String::from_utf8(backend.writer().clone()).unwrap()
+    }
+
+    #[test]
+    fn should_write_nothing_for_identical_frames() {
+        let mut frame = Buffer::new(3, 3);
+        frame.set(1, 1, Cell::new('a', colors::RGBColor(0, 255, 0)));
+        assert_eq!(render(&frame, &frame), "");
+    }
+
+    #[test]
+    fn should_coalesce_runs_and_skip_redundant_colors() {
+        let previous = Buffer::new(2, 4);
+        let mut current = previous.clone();
+        let green = colors::RGBColor(0, 255, 0);
+        current.set(1, 0, Cell::new('a', green));
+        current.set(2, 0, Cell::new('b', green));
+        current.set(0, 1, Cell::new('c', green));
+        assert_eq!(
+            render(&current, &previous),
+            "\x1b[1;2H\x1b[38;2;0;255;0mab\x1b[2;1Hc\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn should_reset_colors_when_erasing_cells() {
+        let previous = Buffer::new(1, 2);
+        let mut current = previous.clone();
+        current.set(0, 0, Cell::new('a', colors::RGBColor(0, 255, 0)));
+        let mut next = current.clone();
+        next.clear();
+        assert_eq!(render(&next, &current), "\x1b[1;1H ");
+    }
+
+    #[test]
+    fn should_quantize_colors_to_the_color_mode() {
+        let previous = Buffer::new(1, 2);
+        let mut current = previous.clone();
+        current.set(0, 0, Cell::new('a', colors::RGBColor(0, 255, 70)));
+        current.set(1, 0, Cell::new('b', colors::RGBColor(0, 250, 80)));
+        assert_eq!(
+            render_in(ColorMode::Ansi256, &current, &previous),
+            "\x1b[1;1H\x1b[38;5;47mab\x1b[0m"
+        );
+        assert_eq!(
+            render_in(ColorMode::Ansi16, &current, &previous),
+            "\x1b[1;1H\x1b[92mab\x1b[0m"
+        );
+        assert_eq!(
+            render_in(ColorMode::None, &current, &previous),
+            "\x1b[1;1Hab"
+        );
+    }
+
+    #[test]
+    fn should_skip_the_right_half_of_double_width_symbols() {
+        let previous = Buffer::new(1, 4);
+        let mut current = previous.clone();
+        let green = colors::RGBColor(0, 255, 0);
+        current.set(0, 0, Cell::new('ア', green));
+        current.set(2, 0, Cell::new('a', green));
+        assert_eq!(
+            render(&current, &previous),
+            "\x1b[1;1H\x1b[38;2;0;255;0mアa\x1b[0m"
+        );
+    }
+}
@@ -1,5 +1,8 @@
 use std::str::FromStr;
 
+use rand::Rng;
+use unicode_width::UnicodeWidthChar;
+
 use crate::helpers::utils;
 
 // =======
@@ -7,6 +10,7 @@ use crate::helpers::utils;
 // =======
 
 ///  The character symbol set to use for the entities
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Clone, Debug)]
 pub enum Symbols {
     ///  Katakana Symbols: Unicode range from 0x30A0 to 0x30A0 + 96 (96 Katakana characters) (e.g. ア, カ, サ, ナ)
@@ -39,60 +43,96 @@ impl FromStr for Symbols {
             "ascii" | "text" | "english" => Ok(Self::ASCII),
             "braille" | "dots" => Ok(Self::Braille),
             "emoji" | "cursed" => Ok(Self::Cursed),
+            "" => Err(ParseError {}),
             x => Ok(Self::Custom(x.to_string())),
         }
     }
 }
 
 impl Symbols {
+    /// Returns the next built-in symbol set, cycling back around to [Symbols::Original].
+    /// Custom symbol sets are followed by [Symbols::Original].
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Original => Self::Binary,
+            Self::Binary => Self::Decimal,
+            Self::Decimal => Self::ASCII,
+            Self::ASCII => Self::Math,
+            Self::Math => Self::Braille,
+            Self::Braille => Self::Cursed,
+            Self::Cursed | Self::Custom(_) => Self::Original,
+        }
+    }
+
+    /// Returns the number of terminal columns the widest symbol in the set occupies.
+    /// The Katakana and emoji symbols are double-width, so they need two columns.
+    /// Only the custom sets are measured, as the widths of the built-in sets are known.
+    pub fn width(&self) -> u16 {
+        match self {
+            Self::Original | Self::Cursed => 2,
+            Self::Binary | Self::Decimal | Self::ASCII | Self::Math | Self::Braille => 1,
+            Self::Custom(s) => s
+                .chars()
+                .filter_map(|c| c.width())
+                .max()
+                .unwrap_or(1)
+                .max(1) as u16,
+        }
+    }
+
     /// Get a random character from the symbol set
-    pub fn get_random(&self) -> char {
+    pub fn get_random<R: Rng + ?Sized>(&self, rng: &mut R) -> char {
         match self {
             Self::Original => {
-                let r = utils::random_between(0x30a0, 0x30a0 + 96) as u32;
-                return std::char::from_u32(r).unwrap_or('0');
+                let r = utils::random_between(rng, 0x30a0, 0x30a0 + 96) as u32;
+                std::char::from_u32(r).unwrap_or('0')
             }
 
             Self::Binary => {
-                let r = utils::random_between(0, 2);
-                return if r == 0 { '0' } else { '1' };
+                let r = utils::random_between(rng, 0, 2);
+                if r == 0 {
+                    '0'
+                } else {
+                    '1'
+                }
             }
 
             Self::Decimal => {
-                let r = utils::random_between(0, 10);
-                return std::char::from_digit(r, 10).unwrap_or('0');
+                let r = utils::random_between(rng, 0, 10);
+                std::char::from_digit(r, 10).unwrap_or('0')
             }
 
             Self::ASCII => {
-                let r = utils::random_between(33, 127) as u32;
-                return std::char::from_u32(r).unwrap_or('0');
+                let r = utils::random_between(rng, 33, 127) as u32;
+                std::char::from_u32(r).unwrap_or('0')
             }
 
             Self::Math => {
                 // Choose a range randomly for different categories of mathematical symbols
-                let category = utils::random_between(0, 4);
+                let category = utils::random_between(rng, 0, 4);
                 let r = match category {
-                    0 => utils::random_between(0x2210, 0x221F) as u32, // Basic Mathematical Operators
-                    1 => utils::random_between(0x2260, 0x226F) as u32, // Relations
-                    2 => utils::random_between(0x2190, 0x21FF) as u32, // Arrows
-                    _ => utils::random_between(0x27C0, 0x27EF) as u32, // Miscellaneous Mathematical Symbols
+                    0 => utils::random_between(rng, 0x2210, 0x221F) as u32, // Basic Mathematical Operators
+                    1 => utils::random_between(rng, 0x2260, 0x226F) as u32, // Relations
+                    2 => utils::random_between(rng, 0x2190, 0x21FF) as u32, // Arrows
+                    _ => utils::random_between(rng, 0x27C0, 0x27EF) as u32, // Miscellaneous Mathematical Symbols
                 };
-                return std::char::from_u32(r).unwrap_or('0');
+                std::char::from_u32(r).unwrap_or('0')
             }
 
             Self::Braille => {
-                let r = utils::random_between(0x2840, 0x2840 + 63) as u32;
-                return std::char::from_u32(r).unwrap_or('0');
+                let r = utils::random_between(rng, 0x2840, 0x2840 + 63) as u32;
+                std::char::from_u32(r).unwrap_or('0')
             }
 
             Self::Cursed => {
-                let r = utils::random_between(0x1f300, 0x1f3f0) as u32;
-                return std::char::from_u32(r).unwrap_or('0');
+                let r = utils::random_between(rng, 0x1f300, 0x1f3f0) as u32;
+                std::char::from_u32(r).unwrap_or('0')
             }
 
             Self::Custom(s) => {
-                let r = utils::random_between(0, s.len());
-                return s.chars().nth(r).unwrap_or('0');
+                // Count the characters rather than the bytes, as the symbols can be multibyte
+                let r = utils::random_between(rng, 0, s.chars().count().max(1));
+                s.chars().nth(r).unwrap_or('0')
             }
         }
     }
@@ -112,3 +152,56 @@ impl std::fmt::Display for ParseError {
 }
 
 impl std::error::Error for ParseError {}
+
+// -----
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_measure_the_widest_symbol() {
+        assert_eq!(Symbols::Original.width(), 2);
+        assert_eq!(Symbols::Cursed.width(), 2);
+        assert_eq!(Symbols::Binary.width(), 1);
+        assert_eq!(Symbols::Braille.width(), 1);
+        assert_eq!(Symbols::Custom("ab".into()).width(), 1);
+        assert_eq!(Symbols::Custom("aア".into()).width(), 2);
+    }
+
+    #[test]
+    fn should_pick_every_symbol_of_a_custom_set() {
+        let symbols = Symbols::Custom("アカ".into());
+        let mut rng = rand::thread_rng();
+        let picked: std::collections::HashSet<char> =
+            (0..100).map(|_| symbols.get_random(&mut rng)).collect();
+        assert_eq!(picked, ['ア', 'カ'].into());
+        assert!("".parse::<Symbols>().is_err());
+    }
+
+    #[test]
+    fn should_know_the_widths_of_the_built_in_sets() {
+        let widest = |range: std::ops::Range<u32>| {
+            range
+                .filter_map(std::char::from_u32)
+                .filter_map(|c| c.width())
+                .max()
+                .unwrap_or(1) as u16
+        };
+        let math = [
+            0x2210..0x221F,
+            0x2260..0x226F,
+            0x2190..0x21FF,
+            0x27C0..0x27EF,
+        ];
+        assert_eq!(Symbols::Original.width(), widest(0x30a0..0x30a0 + 96));
+        assert_eq!(
+            Symbols::Math.width(),
+            math.into_iter().map(widest).max().unwrap()
+        );
+        assert_eq!(Symbols::Braille.width(), widest(0x2840..0x2840 + 63));
+        assert_eq!(Symbols::Cursed.width(), widest(0x1f300..0x1f3f0));
+    }
+}
@@ -0,0 +1,186 @@
+use rand::{rngs::StdRng, SeedableRng};
+
+use matrix_rain::{export, helpers::color_mode::ColorMode, Buffer, Cell, LinearGradient, RGBColor};
+
+//  ======
+//  THEMES
+//  ======
+
+/// A named preset of the options that make up a well-known look
+#[derive(Debug, PartialEq)]
+pub struct Theme {
+    /// The name the theme is selected by
+    pub name: &'static str,
+    /// A short description of the look
+    pub description: &'static str,
+    /// The character symbol set
+    mode: &'static str,
+    /// The color of the streaming entities
+    stream_color: &'static str,
+    /// The color of the leading entity in a stream
+    leading_entity_color: &'static str,
+    /// The gradient the streams fade along, if not the default fade of the stream color
+    stream_gradient: Option<&'static str>,
+    /// The background color, if not the terminal's background
+    background_color: Option<&'static str>,
+}
+
+/// The built-in themes
+pub const THEMES: [Theme; 7] = [
+    Theme {
+        name: "classic",
+        description: "The original green Matrix rain",
+        mode: "original",
+        stream_color: "0,255,70",
+        leading_entity_color: "200,255,200",
+        stream_gradient: None,
+        background_color: None,
+    },
+    Theme {
+        name: "amber",
+        description: "An old amber monochrome terminal",
+        mode: "ascii",
+        stream_color: "#ffb000",
+        leading_entity_color: "#ffe6a0",
+        stream_gradient: Some("#ffb000,#663c00"),
+        background_color: Some("#0a0600"),
+    },
+    Theme {
+        name: "cyberpunk",
+        description: "Neon pink and purple on a deep violet night",
+        mode: "original",
+        stream_color: "#ff2a6d",
+        leading_entity_color: "#05d9e8",
+        stream_gradient: Some("#ff2a6d,#d300c5@0.5,#2a0045"),
+        background_color: Some("#0d0221"),
+    },
+    Theme {
+        name: "ice",
+        description: "Frozen blue braille crystals",
+        mode: "braille",
+        stream_color: "#a5f2f3",
+        leading_entity_color: "#ffffff",
+        stream_gradient: Some("ice"),
+        background_color: Some("#00101a"),
+    },
+    Theme {
+        name: "blood",
+        description: "Dripping crimson on black",
+        mode: "original",
+        stream_color: "#ff1a1a",
+        leading_entity_color: "#ffb0b0",
+        stream_gradient: Some("#ff1a1a,#8a0303@0.5,#2b0000"),
+        background_color: Some("#0a0000"),
+    },
+    Theme {
+        name: "monochrome",
+        description: "Shades of gray, in binary",
+        mode: "binary",
+        stream_color: "#e0e0e0",
+        leading_entity_color: "#ffffff",
+        stream_gradient: Some("#e0e0e0,#303030"),
+        background_color: None,
+    },
+    Theme {
+        name: "solarized",
+        description: "The Solarized dark palette",
+        mode: "ascii",
+        stream_color: "#2aa198",
+        leading_entity_color: "#fdf6e3",
+        stream_gradient: Some("#2aa198,#268bd2@0.5,#073642"),
+        background_color: Some("#002b36"),
+    },
+];
+
+impl Theme {
+    /// Returns the command-line arguments that select the options of the theme.
+    /// The gradient of the theme is only included if `gradient` is set, as it takes precedence
+    /// over the stream color, and would otherwise hide a stream color given explicitly.
+    pub fn args(&self, gradient: bool) -> Vec<&'static str> {
+        let mut args = vec![
+            "--mode",
+            self.mode,
+            "--stream-color",
+            self.stream_color,
+            "--leading-entity-color",
+            self.leading_entity_color,
+        ];
+        if let Some(stream_gradient) = self.stream_gradient.filter(|_| gradient) {
+            args.extend(["--stream-gradient", stream_gradient]);
+        }
+        if let Some(color) = self.background_color {
+            args.extend(["--background-color", color]);
+        }
+        args
+    }
+
+    /// Renders a sample stream in the colors of the theme, lying on its side
+    fn preview(&self, length: u16) -> Buffer {
+        let parse = |color: &str| color.parse::<RGBColor>().unwrap_or(RGBColor(255, 255, 255));
+        let stream_color = parse(self.stream_color);
+        let gradient = self
+            .stream_gradient
+            .and_then(|gradient| gradient.parse().ok())
+            .unwrap_or_else(|| LinearGradient::new(stream_color, stream_color * 0.33));
+        let mode = self.mode.parse().unwrap_or(matrix_rain::Symbols::ASCII);
+
+        let mut buffer = Buffer::new(1, length * mode.width());
+        buffer.set_row_background(0, self.background_color.map(parse));
+
+        // The leader comes last, as if the stream was flowing to the right
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut colors: Vec<_> = gradient.steps(length as usize - 1).collect();
+        colors.reverse();
+        colors.push(parse(self.leading_entity_color));
+        for (i, color) in colors.into_iter().enumerate() {
+            let cell = Cell::new(mode.get_random(&mut rng), color);
+            buffer.set(i as u16 * mode.width(), 0, cell);
+        }
+        buffer
+    }
+}
+
+/// Parses the name of a built-in theme
+pub fn parse(name: &str) -> Result<&'static Theme, String> {
+    THEMES
+        .iter()
+        .find(|theme| theme.name.eq_ignore_ascii_case(name.trim()))
+        .ok_or_else(|| {
+            let names: Vec<_> = THEMES.iter().map(|theme| theme.name).collect();
+            format!("Unknown theme: {name} (available: {})", names.join(", "))
+        })
+}
+
+/// Prints the built-in themes, along with a preview of each one
+pub fn list(color_mode: ColorMode) {
+    for theme in THEMES.iter() {
+        let preview = export::to_ansi(&theme.preview(16), color_mode);
+        println!(
+            "{:<12} {:<46} {}",
+            theme.name,
+            theme.description,
+            preview.trim_end()
+        );
+    }
+}
+
+// -----
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use clap::Parser;
+
+    #[test]
+    fn should_parse_the_options_of_every_theme() {
+        for theme in THEMES.iter() {
+            let args = std::iter::once("matrix-rain").chain(theme.args(true));
+            assert!(Config::try_parse_from(args).is_ok(), "{}", theme.name);
+        }
+        assert_eq!(parse("Amber").map(|theme| theme.name), Ok("amber"));
+        assert!(parse("unknown").is_err());
+    }
+}